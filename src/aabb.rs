@@ -0,0 +1,566 @@
+//!
+//! Axis aligned bounding boxes in 3D space
+//!
+
+use std::ops::Deref;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::matrix::Matrix;
+use crate::plane::Plane;
+use crate::sphere::Sphere;
+use crate::traits::Approximately;
+use crate::traits::Distance;
+use crate::traits::FloatExt;
+use crate::traits::FromLossy;
+use crate::traits::Intersects;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+use crate::Zero;
+
+const EPSILON: Float = Float::EPSILON;
+
+/// The indices of the corners returned by [Aabb::corners] joined by each of the twelve edges
+const EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (2, 3),
+    (4, 5),
+    (6, 7),
+    (0, 2),
+    (1, 3),
+    (4, 6),
+    (5, 7),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// An axis aligned bounding box, spanning from `min` to `max` inclusive
+#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq)]
+pub struct Aabb {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Aabb {
+    /// Constructs a new [Aabb] spanning two opposite corners, in any order
+    pub fn new(a: Point, b: Point) -> Self {
+        let (a, b) = (a.as_vector(), b.as_vector());
+        Self {
+            min: Point::from(a.component_min(&b)),
+            max: Point::from(a.component_max(&b)),
+        }
+    }
+
+    /// Constructs a new [Aabb] from its center and the distance from the center to each face
+    pub fn from_center_and_half_extents(center: Point, half_extents: Vector) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    /// Computes the smallest [Aabb] enclosing every point in `points`
+    ///
+    /// Returns `None` if `points` is empty
+    pub fn from_points<P: Deref<Target = [Point]>>(points: P) -> Option<Self> {
+        let (first, rest) = points.split_first()?;
+        let mut aabb = Self::new(*first, *first);
+        for point in rest {
+            aabb.expand_to(point);
+        }
+        Some(aabb)
+    }
+
+    #[inline]
+    pub fn center(&self) -> Point {
+        self.min + (self.max - self.min) / Float::from(2.0)
+    }
+
+    /// The length of each side of the box
+    #[inline]
+    pub fn size(&self) -> Vector {
+        self.max - self.min
+    }
+
+    /// The distance from the center of the box to each of its faces
+    #[inline]
+    pub fn half_extents(&self) -> Vector {
+        self.size() / Float::from(2.0)
+    }
+
+    #[inline]
+    pub fn volume(&self) -> Float {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+
+    #[inline]
+    pub fn surface_area(&self) -> Float {
+        let size = self.size();
+        Float::from(2.0) * (size.x * size.y + size.x * size.z + size.y * size.z)
+    }
+
+    /// The eight corners of the box
+    ///
+    /// Corner `i` takes its x, y and z components from `max` where bits 0, 1 and 2 of `i`
+    /// are set respectively, and from `min` otherwise
+    pub fn corners(&self) -> [Point; 8] {
+        let (min, max) = (self.min, self.max);
+        std::array::from_fn(|i| {
+            Point::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        })
+    }
+
+    /// Test whether `point` is inside or on the surface of the box
+    #[inline]
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= self.min.x
+            && point.y >= self.min.y
+            && point.z >= self.min.z
+            && point.x <= self.max.x
+            && point.y <= self.max.y
+            && point.z <= self.max.z
+    }
+
+    /// Test whether `other` lies entirely within the box
+    #[inline]
+    pub fn contains_aabb(&self, other: &Aabb) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// Returns the smallest [Aabb] enclosing both this and `other`
+    pub fn union(&self, other: &Aabb) -> Self {
+        Self {
+            min: Point::from(self.min.as_vector().component_min(&other.min.as_vector())),
+            max: Point::from(self.max.as_vector().component_max(&other.max.as_vector())),
+        }
+    }
+
+    /// Grows the box by `amount` on every side
+    ///
+    /// A negative amount shrinks the box, collapsing it to its center rather than inverting it
+    pub fn expand<F: Into<Float>>(&mut self, amount: F) {
+        *self = self.expanded(amount);
+    }
+
+    /// Returns a new [Aabb] grown by `amount` on every side
+    ///
+    /// See [Aabb::expand]
+    pub fn expanded<F: Into<Float>>(&self, amount: F) -> Self {
+        let amount = Vector::from(amount.into());
+        let half_extents = (self.half_extents() + amount).component_max(&Vector::zero());
+        Self::from_center_and_half_extents(self.center(), half_extents)
+    }
+
+    /// Grows the box just enough to enclose `point`
+    pub fn expand_to(&mut self, point: &Point) {
+        *self = self.expanded_to(point);
+    }
+
+    /// Returns a new [Aabb] grown just enough to enclose `point`
+    pub fn expanded_to(&self, point: &Point) -> Self {
+        self.union(&Self::new(*point, *point))
+    }
+
+    /// Returns the point inside or on the surface of the box which is closest to `point`
+    pub fn closest_point(&self, point: &Point) -> Point {
+        Point::from(
+            point
+                .as_vector()
+                .clamp(&self.min.as_vector(), &self.max.as_vector()),
+        )
+    }
+
+    /// Returns the tightest [Aabb] enclosing this box after transformation by `matrix`
+    ///
+    /// `matrix` transforms column vectors as `&Matrix * &Point` does, with any translation
+    /// in column 3 like [Matrix::from_translation]. Matrices in the row-vector layout of
+    /// [Matrix::from_translation_and_orientation] would be misread as projective, use
+    /// [Aabb::transformed_row_vector] for those instead
+    ///
+    /// Affine transforms are handled directly from the matrix elements, projective ones
+    /// by transforming each of the eight corners
+    pub fn transformed(&self, matrix: &Matrix) -> Self {
+        let affine = matrix[3] == [Float::ZERO, Float::ZERO, Float::ZERO, Float::ONE];
+        if !affine {
+            let corners = self.corners().map(|corner| matrix * &corner);
+            return Self::from_points(&corners[..]).unwrap_or(*self);
+        }
+
+        let (min, max) = (self.min.as_vector(), self.max.as_vector());
+        let (min, max) = ([min.x, min.y, min.z], [max.x, max.y, max.z]);
+        let mut out_min = [Float::ZERO; 3];
+        let mut out_max = [Float::ZERO; 3];
+
+        // Each output component is a sum of terms in a single input component,
+        // so it is extremal when each term is
+        for row in 0..3 {
+            out_min[row] = matrix[row][3];
+            out_max[row] = matrix[row][3];
+            for col in 0..3 {
+                let a = matrix[row][col] * min[col];
+                let b = matrix[row][col] * max[col];
+                out_min[row] += Float::min(a, b);
+                out_max[row] += Float::max(a, b);
+            }
+        }
+
+        Self {
+            min: Point::new(out_min[0], out_min[1], out_min[2]),
+            max: Point::new(out_max[0], out_max[1], out_max[2]),
+        }
+    }
+
+    /// Transforms this box by `matrix`, see [Aabb::transformed]
+    pub fn transform(&mut self, matrix: &Matrix) {
+        *self = self.transformed(matrix);
+    }
+
+    /// Returns the tightest [Aabb] enclosing this box after transformation by a `matrix` in
+    /// the row-vector layout, with any translation in row 3
+    ///
+    /// This is the layout of [Matrix::from_orientation], [Matrix::from_translation_and_orientation]
+    /// and [Matrix::from_translation_orientation_and_scale], see [Aabb::transformed]
+    pub fn transformed_row_vector(&self, matrix: &Matrix) -> Self {
+        self.transformed(&matrix.transposed())
+    }
+
+    /// Transforms this box by a `matrix` in the row-vector layout, see
+    /// [Aabb::transformed_row_vector]
+    pub fn transform_row_vector(&mut self, matrix: &Matrix) {
+        *self = self.transformed_row_vector(matrix);
+    }
+}
+
+static AABB_EXPECTED_MESSAGE: &str =
+    "expected non-empty point set when converting to bounding box. use member function instead to handle possible failure";
+
+impl<P: Deref<Target = [Point]>> From<P> for Aabb {
+    fn from(points: P) -> Self {
+        Self::from_points(points).expect(AABB_EXPECTED_MESSAGE)
+    }
+}
+
+impl Approximately for Aabb {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        self.min.approximately(&other.min, epsilon) && self.max.approximately(&other.max, epsilon)
+    }
+}
+
+impl Distance for Aabb {
+    /// The squared length of the gap between two boxes, zero if they overlap
+    fn distance_to_sq(&self, other: &Self) -> Float {
+        let before = self.min - other.max;
+        let after = other.min - self.max;
+        before
+            .component_max(&after)
+            .component_max(&Vector::zero())
+            .length_sq()
+    }
+}
+
+impl Distance<Point> for Aabb {
+    /// The squared distance from the point to the box, zero if it is inside
+    fn distance_to_sq(&self, other: &Point) -> Float {
+        self.closest_point(other).distance_to_sq(other)
+    }
+}
+
+impl Intersects for Aabb {
+    /// The overlapping region of both boxes
+    type Intersection = Option<Aabb>;
+
+    fn interesects(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && self.min.y <= other.max.y
+            && self.min.z <= other.max.z
+            && other.min.x <= self.max.x
+            && other.min.y <= self.max.y
+            && other.min.z <= self.max.z
+    }
+
+    fn intersection(&self, other: &Self) -> Self::Intersection {
+        self.interesects(other).then(|| Self {
+            min: Point::from(self.min.as_vector().component_max(&other.min.as_vector())),
+            max: Point::from(self.max.as_vector().component_min(&other.max.as_vector())),
+        })
+    }
+}
+
+impl Intersects<Sphere> for Aabb {
+    /// The point on or inside the box which is closest to the center of the sphere
+    type Intersection = Option<Point>;
+
+    fn interesects(&self, other: &Sphere) -> bool {
+        self.distance_to_sq(&other.center) <= other.radius * other.radius
+    }
+
+    fn intersection(&self, other: &Sphere) -> Self::Intersection {
+        let closest = self.closest_point(&other.center);
+        (closest.distance_to_sq(&other.center) <= other.radius * other.radius).then_some(closest)
+    }
+}
+
+impl Intersects<Plane> for Aabb {
+    /// The vertices of the polygon where the plane cuts through the box, wound
+    /// counter-clockwise about the plane normal
+    type Intersection = Option<Vec<Point>>;
+
+    fn interesects(&self, other: &Plane) -> bool {
+        let extents = self.half_extents();
+        let n = other.norm;
+        let radius = extents.x * n.x.abs() + extents.y * n.y.abs() + extents.z * n.z.abs();
+        other.distance_to(self.center()).abs() <= radius
+    }
+
+    fn intersection(&self, other: &Plane) -> Self::Intersection {
+        if !self.interesects(other) {
+            return None;
+        }
+
+        let corners = self.corners();
+        let distances = corners.map(|corner| other.distance_to(corner));
+
+        let mut polygon: Vec<Point> = Vec::with_capacity(6);
+        let mut push = |point: Point| {
+            if !polygon.iter().any(|p| p.approximately(&point, EPSILON)) {
+                polygon.push(point);
+            }
+        };
+
+        for (a, b) in EDGES {
+            let (da, db) = (distances[a], distances[b]);
+            if da == Float::ZERO {
+                push(corners[a]);
+            }
+            if db == Float::ZERO {
+                push(corners[b]);
+            }
+            if (da < Float::ZERO && db > Float::ZERO) || (da > Float::ZERO && db < Float::ZERO) {
+                let t = da / (da - db);
+                push(corners[a] + (corners[b] - corners[a]) * t);
+            }
+        }
+
+        // Order the vertices by their angle about the centroid, within the plane
+        let count = Float::from_lossy(polygon.len() as i64);
+        let centroid = polygon
+            .iter()
+            .fold(Vector::zero(), |sum, p| sum + p.as_vector())
+            / count;
+        let u = other.norm.orthogonal().normalized();
+        let v = other.norm.normalized().cross(&u);
+        let angle = |p: &Point| {
+            let offset = p.as_vector() - centroid;
+            Float::atan2(offset.dot(&v), offset.dot(&u))
+        };
+        polygon.sort_by(|a, b| {
+            angle(a)
+                .partial_cmp(&angle(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Some(polygon)
+    }
+}
+
+#[cfg(test)]
+mod aabb_tests {
+    use super::*;
+    use crate::bivec::Bivector;
+    use crate::constant::PI;
+    use crate::rotor::Rotor;
+
+    const EPSILON: Float = 1e-9;
+
+    fn unit() -> Aabb {
+        Aabb::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn new_orders_corners() {
+        let aabb = Aabb::new(Point::new(1.0, -2.0, 3.0), Point::new(-1.0, 2.0, -3.0));
+        assert_eq!(aabb.min, Point::new(-1.0, -2.0, -3.0));
+        assert_eq!(aabb.max, Point::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn from_points() {
+        let points = vec![
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(-2.0, 3.0, 0.5),
+            Point::new(0.0, -1.0, 4.0),
+        ];
+        let aabb = Aabb::from(points);
+        assert_eq!(aabb.min, Point::new(-2.0, -1.0, 0.0));
+        assert_eq!(aabb.max, Point::new(1.0, 3.0, 4.0));
+        assert!(Aabb::from_points(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn measurements() {
+        let aabb = Aabb::new(Point::origin(), Point::new(1.0, 2.0, 3.0));
+        assert_eq!(aabb.center(), Point::new(0.5, 1.0, 1.5));
+        assert_eq!(aabb.size(), Vector::new(1.0, 2.0, 3.0));
+        assert_eq!(aabb.volume(), 6.0);
+        assert_eq!(aabb.surface_area(), 22.0);
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let a = Aabb::new(Point::origin(), Point::new(2.0, 2.0, 2.0));
+        let b = Aabb::new(Point::new(1.0, 1.0, 1.0), Point::new(3.0, 3.0, 3.0));
+        let c = Aabb::new(Point::new(5.0, 5.0, 5.0), Point::new(6.0, 6.0, 6.0));
+
+        let union = a.union(&b);
+        assert_eq!(union.min, Point::origin());
+        assert_eq!(union.max, Point::new(3.0, 3.0, 3.0));
+
+        let overlap = a.intersection(&b).unwrap();
+        assert_eq!(overlap.min, Point::new(1.0, 1.0, 1.0));
+        assert_eq!(overlap.max, Point::new(2.0, 2.0, 2.0));
+
+        assert!(!a.interesects(&c));
+        assert!(a.intersection(&c).is_none());
+    }
+
+    #[test]
+    fn expansion() {
+        let mut aabb = unit();
+        aabb.expand(1.0);
+        assert!(aabb.approximately(
+            Aabb::new(Point::new(-2.0, -2.0, -2.0), Point::new(2.0, 2.0, 2.0)),
+            EPSILON
+        ));
+
+        let collapsed = unit().expanded(-5.0);
+        assert_eq!(collapsed.min, collapsed.max);
+
+        let grown = unit().expanded_to(&Point::new(3.0, 0.0, -4.0));
+        assert_eq!(grown.min, Point::new(-1.0, -1.0, -4.0));
+        assert_eq!(grown.max, Point::new(3.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn contains() {
+        let aabb = unit();
+        assert!(aabb.contains(&Point::origin()));
+        assert!(aabb.contains(&Point::new(1.0, 1.0, 1.0)));
+        assert!(!aabb.contains(&Point::new(1.0, 1.5, 0.0)));
+        assert!(aabb.contains_aabb(&unit().expanded(-0.5)));
+        assert!(!aabb.contains_aabb(&unit().expanded(0.5)));
+    }
+
+    #[test]
+    fn closest_point_and_distance() {
+        let aabb = unit();
+        let outside = Point::new(4.0, 5.0, 0.0);
+        assert_eq!(aabb.closest_point(&outside), Point::new(1.0, 1.0, 0.0));
+        assert!(aabb.distance_to(&outside).approximately(5.0, EPSILON));
+        assert_eq!(aabb.distance_to_sq(&Point::origin()), 0.0);
+
+        let other = Aabb::new(Point::new(4.0, 5.0, -1.0), Point::new(6.0, 6.0, 1.0));
+        assert!(aabb.distance_to(&other).approximately(5.0, EPSILON));
+        assert_eq!(aabb.distance_to_sq(&unit().expanded(1.0)), 0.0);
+    }
+
+    #[test]
+    fn transform_affine_is_tight() {
+        // A quarter turn about z followed by a translation
+        let rotation = Rotor::from_angle_and_plane(PI / 2.0, Bivector::new(1.0, 0.0, 0.0));
+        let mut matrix = Matrix::from_orientation(rotation).transposed();
+        matrix[0][3] = 10.0;
+
+        let aabb = Aabb::new(Point::origin(), Point::new(1.0, 2.0, 3.0));
+        let transformed = aabb.transformed(&matrix);
+        let expected = Aabb::from_points(&aabb.corners().map(|c| &matrix * &c)[..]).unwrap();
+        assert!(transformed.approximately(expected, EPSILON));
+        assert!(transformed
+            .size()
+            .approximately(Vector::new(2.0, 1.0, 3.0), EPSILON));
+    }
+
+    #[test]
+    fn transform_row_vector_layout() {
+        // Scale x by 2, a quarter turn about z taking x to y, then translate by 10 along x
+        let rotation = Rotor::from_angle_and_plane(PI / 2.0, Bivector::new(1.0, 0.0, 0.0));
+        let matrix = Matrix::from_translation_orientation_and_scale(
+            Vector::new(10.0, 0.0, 0.0),
+            rotation,
+            Vector::new(2.0, 1.0, 1.0),
+        );
+
+        let aabb = Aabb::new(Point::origin(), Point::new(1.0, 2.0, 3.0));
+        let expected = Aabb::new(Point::new(8.0, 0.0, 0.0), Point::new(10.0, 2.0, 3.0));
+        assert!(aabb
+            .transformed_row_vector(&matrix)
+            .approximately(expected, EPSILON));
+
+        let mut transformed = aabb;
+        transformed.transform_row_vector(&matrix);
+        assert!(transformed.approximately(expected, EPSILON));
+
+        let rigid = Matrix::from_translation_and_orientation(Point::new(0.0, 0.0, -5.0), rotation);
+        let expected = Aabb::new(Point::new(-2.0, 0.0, -5.0), Point::new(0.0, 1.0, -2.0));
+        assert!(aabb
+            .transformed_row_vector(&rigid)
+            .approximately(expected, EPSILON));
+    }
+
+    #[test]
+    fn transform_rotated_grows() {
+        // An eighth turn grows a unit cube by a factor of √2 in x and y
+        let rotation = Rotor::from_angle_and_plane(PI / 4.0, Bivector::new(1.0, 0.0, 0.0));
+        let matrix = Matrix::from_orientation(rotation);
+        let transformed = unit().transformed(&matrix);
+        let diagonal = Float::sqrt(2.0);
+        assert!(transformed
+            .max
+            .approximately(&Point::new(diagonal, diagonal, 1.0), EPSILON));
+    }
+
+    #[test]
+    fn intersects_sphere() {
+        let aabb = unit();
+        let touching = Sphere::new(Point::new(3.0, 0.0, 0.0), 2.0);
+        let apart = Sphere::new(Point::new(3.0, 3.0, 0.0), 2.0);
+        assert!(aabb.interesects(&touching));
+        assert_eq!(
+            aabb.intersection(&touching),
+            Some(Point::new(1.0, 0.0, 0.0))
+        );
+        assert!(!aabb.interesects(&apart));
+        assert!(aabb.intersection(&apart).is_none());
+    }
+
+    #[test]
+    fn intersects_plane() {
+        let aabb = unit();
+        let through = Plane::new(Vector::unit_z(), 0.5);
+        let diagonal = Plane::new(Vector::new(1.0, 1.0, 1.0), 0.0);
+        let apart = Plane::new(Vector::unit_x(), 2.0);
+
+        let square = aabb.intersection(&through).unwrap();
+        assert_eq!(square.len(), 4);
+        assert!(square.iter().all(|p| p.z.approximately(0.5, EPSILON)));
+
+        let hexagon = aabb.intersection(&diagonal).unwrap();
+        assert_eq!(hexagon.len(), 6);
+        assert!(hexagon
+            .iter()
+            .all(|p| diagonal.distance_to(*p).approximately(0.0, EPSILON)));
+
+        // Consecutive vertices wind counter-clockwise about the normal
+        let centroid = Point::origin();
+        let winding = (hexagon[0] - centroid).cross(&(hexagon[1] - centroid));
+        assert!(winding.dot(&diagonal.norm) > 0.0);
+
+        assert!(!aabb.interesects(&apart));
+        assert!(aabb.intersection(&apart).is_none());
+    }
+}
@@ -5,10 +5,20 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::rotor::Rotor;
 use crate::traits::FloatExt;
 use crate::Approximately;
 use crate::Float;
 use crate::Vector;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Div;
+use std::ops::DivAssign;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 #[repr(C)]
@@ -64,9 +74,13 @@ impl Bivector {
         }
     }
 
+    /// Constructs the [Bivector] of the plane orthogonal to `axis`
+    ///
+    /// The orientation is chosen so that a rotation in the resulting plane is a
+    /// right-handed rotation about `axis`
     #[inline]
     pub fn from_axis_vector(axis: Vector) -> Self {
-        Self::new(axis.z, axis.y, axis.x)
+        Self::new(axis.z, -axis.y, axis.x)
     }
 
     #[inline]
@@ -83,14 +97,185 @@ impl Bivector {
         }
     }
 
+    /// Returns the exponential of this [Bivector], the inverse of [Rotor::log]
+    ///
+    /// The result rotates in the plane of this [Bivector] by an angle equal to its magnitude,
+    /// the same as [Rotor::from_angular_velocity] over one unit of time
+    #[inline]
+    pub fn exp(&self) -> Rotor {
+        Rotor::from_angular_velocity(*self, Float::ONE)
+    }
+
+    /// Returns the axis [Vector] orthogonal to this plane, the inverse of [Bivector::from_axis_vector]
+    ///
+    /// A rotation in this plane is a right-handed rotation about the returned axis, and
+    /// the axis has the same magnitude as this [Bivector]
+    #[inline]
+    pub fn to_axis_vector(&self) -> Vector {
+        Vector::new(self.yz, -self.xz, self.xy)
+    }
+
+    /// Computes the Euclidean dot product of two [Bivector]'s, the sum of the products of
+    /// their components
+    ///
+    /// This is the same as the dot product of their axis vectors, see [Bivector::to_axis_vector]
+    #[inline]
+    pub fn dot(&self, other: &Self) -> Float {
+        self.xy * other.xy + self.xz * other.xz + self.yz * other.yz
+    }
+
+    /// Computes the inner product of two [Bivector]'s in the geometric algebra of 3D space
+    ///
+    /// Bivectors square to negative scalars, so this is the negated [Bivector::dot], and
+    /// a unit [Bivector] has an inner product of `-1` with itself
+    #[inline]
+    pub fn inner(&self, other: &Self) -> Float {
+        -self.dot(other)
+    }
+
+    /// Computes the squared magnitude of this [Bivector]
+    ///
+    /// Slightly faster than [Bivector::magnitude()]
     #[inline]
     pub fn magnitude_sq(&self) -> Float {
-        self.xy * self.xy
+        self.dot(self)
     }
 
+    /// Computes the magnitude of this [Bivector], the area of the oriented plane it represents
     #[inline]
     pub fn magnitude(&self) -> Float {
-        self.xy
+        self.magnitude_sq().sqrt()
+    }
+
+    /// Computes and returns a [Bivector] of unit magnitude in the same plane as this [Bivector]
+    #[inline]
+    pub fn normalized(&self) -> Self {
+        let magnitude = self.magnitude();
+        Self::new(
+            self.xy / magnitude,
+            self.xz / magnitude,
+            self.yz / magnitude,
+        )
+    }
+
+    /// Normalizes this [Bivector] in place
+    #[inline]
+    pub fn normalize(&mut self) {
+        *self = self.normalized();
+    }
+}
+
+macro_rules! bivector_componentwise_binop {
+    ($lhs:ty, $rhs:ty, $func:ident, $trait:ident) => {
+        impl $trait<$rhs> for $lhs {
+            type Output = Bivector;
+            fn $func(self, other: $rhs) -> Self::Output {
+                Self::Output {
+                    xy: Float::$func(self.xy, other.xy),
+                    xz: Float::$func(self.xz, other.xz),
+                    yz: Float::$func(self.yz, other.yz),
+                }
+            }
+        }
+    };
+}
+
+bivector_componentwise_binop!(Bivector, Bivector, add, Add);
+bivector_componentwise_binop!(&Bivector, Bivector, add, Add);
+bivector_componentwise_binop!(Bivector, &Bivector, add, Add);
+bivector_componentwise_binop!(&Bivector, &Bivector, add, Add);
+
+bivector_componentwise_binop!(Bivector, Bivector, sub, Sub);
+bivector_componentwise_binop!(&Bivector, Bivector, sub, Sub);
+bivector_componentwise_binop!(Bivector, &Bivector, sub, Sub);
+bivector_componentwise_binop!(&Bivector, &Bivector, sub, Sub);
+
+macro_rules! bivector_scalar_binop {
+    ($lhs:ty, $rhs:ty, $func:ident, $trait:ident) => {
+        impl $trait<$rhs> for $lhs {
+            type Output = Bivector;
+            fn $func(self, other: $rhs) -> Self::Output {
+                Self::Output {
+                    xy: Float::$func(self.xy, other),
+                    xz: Float::$func(self.xz, other),
+                    yz: Float::$func(self.yz, other),
+                }
+            }
+        }
+    };
+}
+
+bivector_scalar_binop!(Bivector, Float, mul, Mul);
+bivector_scalar_binop!(&Bivector, Float, mul, Mul);
+bivector_scalar_binop!(Bivector, &Float, mul, Mul);
+bivector_scalar_binop!(&Bivector, &Float, mul, Mul);
+
+bivector_scalar_binop!(Bivector, Float, div, Div);
+bivector_scalar_binop!(&Bivector, Float, div, Div);
+bivector_scalar_binop!(Bivector, &Float, div, Div);
+bivector_scalar_binop!(&Bivector, &Float, div, Div);
+
+macro_rules! bivector_mul_reversed {
+    ($lhs:ty, $rhs:ty) => {
+        impl Mul<$rhs> for $lhs {
+            type Output = Bivector;
+            fn mul(self, other: $rhs) -> Self::Output {
+                other * self
+            }
+        }
+    };
+}
+
+bivector_mul_reversed!(Float, Bivector);
+bivector_mul_reversed!(&Float, Bivector);
+bivector_mul_reversed!(Float, &Bivector);
+bivector_mul_reversed!(&Float, &Bivector);
+
+macro_rules! bivector_assignment_op {
+    ($rhs:ty, $func:ident, $trait:ident) => {
+        impl $trait<$rhs> for Bivector {
+            fn $func(&mut self, other: $rhs) {
+                Float::$func(&mut self.xy, other.xy);
+                Float::$func(&mut self.xz, other.xz);
+                Float::$func(&mut self.yz, other.yz);
+            }
+        }
+    };
+}
+
+bivector_assignment_op!(Bivector, add_assign, AddAssign);
+bivector_assignment_op!(&Bivector, add_assign, AddAssign);
+bivector_assignment_op!(Bivector, sub_assign, SubAssign);
+bivector_assignment_op!(&Bivector, sub_assign, SubAssign);
+
+macro_rules! bivector_scalar_assignment_op {
+    ($rhs:ty, $func:ident, $trait:ident) => {
+        impl $trait<$rhs> for Bivector {
+            fn $func(&mut self, other: $rhs) {
+                Float::$func(&mut self.xy, other);
+                Float::$func(&mut self.xz, other);
+                Float::$func(&mut self.yz, other);
+            }
+        }
+    };
+}
+
+bivector_scalar_assignment_op!(Float, mul_assign, MulAssign);
+bivector_scalar_assignment_op!(Float, div_assign, DivAssign);
+
+impl Neg for Bivector {
+    type Output = Bivector;
+
+    fn neg(self) -> Self::Output {
+        Bivector::new(-self.xy, -self.xz, -self.yz)
+    }
+}
+
+impl Neg for &Bivector {
+    type Output = Bivector;
+
+    fn neg(self) -> Self::Output {
+        -*self
     }
 }
 
@@ -101,3 +286,79 @@ impl Approximately for Bivector {
             && self.yz.approximately(other.yz, epsilon)
     }
 }
+
+#[cfg(test)]
+mod bivec_tests {
+    use super::*;
+
+    const EPSILON: Float = 1e-12;
+
+    #[test]
+    fn magnitude() {
+        let b = Bivector::new(2.0, -3.0, 6.0);
+        assert!(b.magnitude_sq().approximately(49.0, EPSILON));
+        assert!(b.magnitude().approximately(7.0, EPSILON));
+        assert!(Bivector::new(0.0, 0.0, 4.0)
+            .magnitude()
+            .approximately(4.0, EPSILON));
+    }
+
+    #[test]
+    fn normalization() {
+        let b = Bivector::new(0.0, 3.0, -4.0).normalized();
+        assert!(b.approximately(Bivector::new(0.0, 0.6, -0.8), EPSILON));
+
+        let mut c = Bivector::new(1.0, 1.0, 1.0);
+        c.normalize();
+        assert!(c.magnitude().approximately(1.0, EPSILON));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Bivector::new(1.0, 2.0, 3.0);
+        let b = Bivector::new(-1.0, 0.5, 2.0);
+        assert_eq!(a + b, Bivector::new(0.0, 2.5, 5.0));
+        assert_eq!(a - b, Bivector::new(2.0, 1.5, 1.0));
+        assert_eq!(a * 2.0, Bivector::new(2.0, 4.0, 6.0));
+        assert_eq!(2.0 * &a, a * 2.0);
+        assert_eq!(a / 2.0, Bivector::new(0.5, 1.0, 1.5));
+        assert_eq!(-a, Bivector::new(-1.0, -2.0, -3.0));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        c *= 4.0;
+        c /= 2.0;
+        assert_eq!(c, b * 2.0);
+    }
+
+    #[test]
+    fn inner_product() {
+        let a = Bivector::new(1.0, 2.0, 3.0);
+        let b = Bivector::new(4.0, -5.0, 6.0);
+        assert!(a.dot(&b).approximately(12.0, EPSILON));
+        assert!(a.inner(&b).approximately(-12.0, EPSILON));
+        assert!(Bivector::unit_xy()
+            .inner(&Bivector::unit_xy())
+            .approximately(-1.0, EPSILON));
+        assert!(Bivector::unit_xy()
+            .inner(&Bivector::unit_yz())
+            .approximately(0.0, EPSILON));
+    }
+
+    #[test]
+    fn axis_vector_round_trip() {
+        let axis = Vector::new(1.0, -2.0, 3.0);
+        let b = Bivector::from_axis_vector(axis);
+        assert!(b.to_axis_vector().approximately(axis, EPSILON));
+        assert!(Bivector::from_wedge(Vector::unit_x(), Vector::unit_y())
+            .to_axis_vector()
+            .approximately(Vector::unit_z(), EPSILON));
+
+        // The dot product agrees with that of the axis vectors
+        let other = Vector::new(0.5, 4.0, -1.0);
+        let dot = b.dot(&Bivector::from_axis_vector(other));
+        assert!(dot.approximately(axis.dot(&other), EPSILON));
+    }
+}
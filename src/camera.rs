@@ -0,0 +1,564 @@
+//!
+//! Camera controllers
+//!
+//! Orbit, fly and arcball cameras built on [Matrix::look_at] and [Matrix::look_toward]. Each
+//! camera keeps a goal state which input moves immediately, and a current state which follows
+//! the goal by exponential smoothing as [Camera::update] is called every frame. The world is
+//! Y-up and the view matrices are right-handed, looking down -Z
+//!
+//! View matrices transform column vectors like the projections of [crate::matrix], so they
+//! are the transpose of [Matrix::look_at] and combine with a projection as `projection * view`,
+//! see [Camera::view_projection]
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::constant::PI;
+use crate::matrix::Matrix;
+use crate::rotor::Rotor;
+use crate::traits::FloatExt;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+
+/// Keeps the pitch of orbit and fly cameras short of straight up or down, where the view
+/// direction would be parallel to the up vector
+const PITCH_LIMIT: Float = PI / 2.0 - 1e-3;
+
+/// The closest an [OrbitCamera] or [ArcballCamera] may zoom toward its target
+const MIN_DISTANCE: Float = 1e-6;
+
+/// The behaviour shared by every camera controller
+pub trait Camera {
+    /// Advances the smoothing by the frame time `dt` in seconds
+    fn update(&mut self, dt: Float);
+
+    /// Jumps straight to the goal state, skipping the smoothing
+    fn snap(&mut self);
+
+    /// The current position of the camera
+    fn eye(&self) -> Point;
+
+    /// The current view [Matrix], which transforms world space column vectors into view space
+    /// as `&view * &point`. This is the transpose of [Matrix::look_at]
+    fn view_matrix(&self) -> Matrix;
+
+    /// The current view [Matrix] followed by `projection`, mapping world space to clip space as
+    /// expected by [crate::frustum::Frustum::from_matrix]
+    fn view_projection(&self, projection: &Matrix) -> Matrix {
+        projection * &self.view_matrix()
+    }
+}
+
+/// Returns how far to move toward the goal over `dt` seconds, where `smoothing` is the time
+/// constant of the exponential decay. A smoothing of zero snaps straight to the goal
+fn smoothing_weight(smoothing: Float, dt: Float) -> Float {
+    if smoothing <= Float::ZERO {
+        Float::ONE
+    } else {
+        Float::ONE - (-dt / smoothing).exp()
+    }
+}
+
+fn lerp(from: Float, to: Float, weight: Float) -> Float {
+    from + (to - from) * weight
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+struct Orbit {
+    target: Point,
+    distance: Float,
+    yaw: Float,
+    pitch: Float,
+}
+
+impl Orbit {
+    /// The unit vector from the target toward the eye
+    fn direction(&self) -> Vector {
+        let (yaw, pitch) = (self.yaw, self.pitch);
+        Vector::new(
+            pitch.cos() * yaw.sin(),
+            pitch.sin(),
+            pitch.cos() * yaw.cos(),
+        )
+    }
+
+    fn eye(&self) -> Point {
+        self.target + self.direction() * self.distance
+    }
+
+    fn lerp(&self, to: &Self, weight: Float) -> Self {
+        Self {
+            target: Point::from(self.target.as_vector().lerp(&to.target.as_vector(), weight)),
+            distance: lerp(self.distance, to.distance, weight),
+            yaw: lerp(self.yaw, to.yaw, weight),
+            pitch: lerp(self.pitch, to.pitch, weight),
+        }
+    }
+}
+
+/// A camera circling a target point, placed by its distance and spherical angles
+///
+/// At zero yaw and pitch the camera sits on the +Z side of the target. Positive yaw moves it
+/// counter-clockwise about +Y seen from above, and positive pitch raises it
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct OrbitCamera {
+    /// The time constant of the smoothing in seconds, zero to disable it
+    pub smoothing: Float,
+    goal: Orbit,
+    current: Orbit,
+}
+
+impl OrbitCamera {
+    /// Constructs a new [OrbitCamera] without smoothing
+    pub fn new<F: Into<Float>>(target: Point, distance: F, yaw: F, pitch: F) -> Self {
+        let orbit = Orbit {
+            target,
+            distance: distance.into().max(MIN_DISTANCE),
+            yaw: yaw.into(),
+            pitch: pitch.into().clamp(-PITCH_LIMIT, PITCH_LIMIT),
+        };
+        Self {
+            smoothing: Float::ZERO,
+            goal: orbit,
+            current: orbit,
+        }
+    }
+
+    /// Returns a copy of this [OrbitCamera] with the smoothing time constant `smoothing`
+    pub fn with_smoothing<F: Into<Float>>(&self, smoothing: F) -> Self {
+        Self {
+            smoothing: smoothing.into(),
+            ..*self
+        }
+    }
+
+    pub fn target(&self) -> Point {
+        self.current.target
+    }
+
+    pub fn distance(&self) -> Float {
+        self.current.distance
+    }
+
+    /// The current yaw and pitch in radians
+    pub fn angles(&self) -> (Float, Float) {
+        (self.current.yaw, self.current.pitch)
+    }
+
+    /// Turns the camera about the target, keeping the pitch short of the poles
+    pub fn rotate<F: Into<Float>>(&mut self, yaw: F, pitch: F) {
+        self.goal.yaw += yaw.into();
+        self.goal.pitch = (self.goal.pitch + pitch.into()).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+
+    /// Scales the distance to the target, so factors below one zoom in
+    pub fn zoom<F: Into<Float>>(&mut self, factor: F) {
+        self.goal.distance = (self.goal.distance * factor.into()).max(MIN_DISTANCE);
+    }
+
+    /// Moves the target, and the camera with it, across the view
+    pub fn pan<F: Into<Float>>(&mut self, right: F, up: F) {
+        let back = self.goal.direction();
+        let side = Vector::unit_y().cross(&back).normalized();
+        let above = back.cross(&side);
+        self.goal.target = self.goal.target + side * right.into() + above * up.into();
+    }
+
+    pub fn set_target(&mut self, target: Point) {
+        self.goal.target = target;
+    }
+}
+
+impl Camera for OrbitCamera {
+    fn update(&mut self, dt: Float) {
+        let weight = smoothing_weight(self.smoothing, dt);
+        self.current = self.current.lerp(&self.goal, weight);
+    }
+
+    fn snap(&mut self) {
+        self.current = self.goal;
+    }
+
+    fn eye(&self) -> Point {
+        self.current.eye()
+    }
+
+    fn view_matrix(&self) -> Matrix {
+        Matrix::look_at(self.eye(), self.current.target, Vector::unit_y()).transposed()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+struct Fly {
+    position: Point,
+    yaw: Float,
+    pitch: Float,
+}
+
+impl Fly {
+    fn orientation(&self) -> Rotor {
+        Rotor::from_axis_angle(Vector::unit_y(), self.yaw)
+            * Rotor::from_axis_angle(Vector::unit_x(), self.pitch)
+    }
+
+    fn lerp(&self, to: &Self, weight: Float) -> Self {
+        Self {
+            position: Point::from(
+                self.position
+                    .as_vector()
+                    .lerp(&to.position.as_vector(), weight),
+            ),
+            yaw: lerp(self.yaw, to.yaw, weight),
+            pitch: lerp(self.pitch, to.pitch, weight),
+        }
+    }
+}
+
+/// A first-person camera that flies freely, turned by yaw about +Y and pitch about its own +X
+///
+/// At zero yaw and pitch the camera looks down -Z. Positive yaw turns it left and positive
+/// pitch tilts it up
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct FlyCamera {
+    /// The time constant of the smoothing in seconds, zero to disable it
+    pub smoothing: Float,
+    goal: Fly,
+    current: Fly,
+}
+
+impl FlyCamera {
+    /// Constructs a new [FlyCamera] without smoothing
+    pub fn new<F: Into<Float>>(position: Point, yaw: F, pitch: F) -> Self {
+        let fly = Fly {
+            position,
+            yaw: yaw.into(),
+            pitch: pitch.into().clamp(-PITCH_LIMIT, PITCH_LIMIT),
+        };
+        Self {
+            smoothing: Float::ZERO,
+            goal: fly,
+            current: fly,
+        }
+    }
+
+    /// Returns a copy of this [FlyCamera] with the smoothing time constant `smoothing`
+    pub fn with_smoothing<F: Into<Float>>(&self, smoothing: F) -> Self {
+        Self {
+            smoothing: smoothing.into(),
+            ..*self
+        }
+    }
+
+    /// The current yaw and pitch in radians
+    pub fn angles(&self) -> (Float, Float) {
+        (self.current.yaw, self.current.pitch)
+    }
+
+    /// The current orientation, rotating -Z onto the view direction
+    pub fn orientation(&self) -> Rotor {
+        self.current.orientation()
+    }
+
+    /// The current unit view direction
+    pub fn forward(&self) -> Vector {
+        (-Vector::unit_z()).rotated_by(&self.orientation())
+    }
+
+    /// The current unit direction to the right of the view
+    pub fn right(&self) -> Vector {
+        Vector::unit_x().rotated_by(&self.orientation())
+    }
+
+    /// Turns the camera, keeping the pitch short of straight up or down
+    pub fn rotate<F: Into<Float>>(&mut self, yaw: F, pitch: F) {
+        self.goal.yaw += yaw.into();
+        self.goal.pitch = (self.goal.pitch + pitch.into()).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+
+    /// Moves the camera along its own view and right directions, and along world +Y
+    pub fn translate<F: Into<Float>>(&mut self, forward: F, right: F, up: F) {
+        let orientation = self.goal.orientation();
+        let offset = (-Vector::unit_z()).rotated_by(&orientation) * forward.into()
+            + Vector::unit_x().rotated_by(&orientation) * right.into()
+            + Vector::unit_y() * up.into();
+        self.goal.position += offset;
+    }
+
+    pub fn set_position(&mut self, position: Point) {
+        self.goal.position = position;
+    }
+}
+
+impl Camera for FlyCamera {
+    fn update(&mut self, dt: Float) {
+        let weight = smoothing_weight(self.smoothing, dt);
+        self.current = self.current.lerp(&self.goal, weight);
+    }
+
+    fn snap(&mut self) {
+        self.current = self.goal;
+    }
+
+    fn eye(&self) -> Point {
+        self.current.position
+    }
+
+    fn view_matrix(&self) -> Matrix {
+        Matrix::look_toward(self.eye(), self.forward(), Vector::unit_y()).transposed()
+    }
+}
+
+/// A camera rotated about a target by dragging on a virtual trackball
+///
+/// Unlike an [OrbitCamera] it has no poles, so it can roll and turn upside down. Drags are
+/// given in normalized coordinates with +Y up, where the unit circle spans the trackball
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct ArcballCamera {
+    /// The time constant of the smoothing in seconds, zero to disable it
+    pub smoothing: Float,
+    pub target: Point,
+    goal: (Rotor, Float),
+    current: (Rotor, Float),
+}
+
+impl ArcballCamera {
+    /// Constructs a new [ArcballCamera] on the +Z side of `target`, without smoothing
+    pub fn new<F: Into<Float>>(target: Point, distance: F) -> Self {
+        let state = (Rotor::identity(), distance.into().max(MIN_DISTANCE));
+        Self {
+            smoothing: Float::ZERO,
+            target,
+            goal: state,
+            current: state,
+        }
+    }
+
+    /// Returns a copy of this [ArcballCamera] with the smoothing time constant `smoothing`
+    pub fn with_smoothing<F: Into<Float>>(&self, smoothing: F) -> Self {
+        Self {
+            smoothing: smoothing.into(),
+            ..*self
+        }
+    }
+
+    /// The current orientation, rotating the view space axes into world space
+    pub fn orientation(&self) -> Rotor {
+        self.current.0
+    }
+
+    pub fn distance(&self) -> Float {
+        self.current.1
+    }
+
+    /// Turns the scene as if the trackball were dragged from `from` to `to`
+    pub fn drag(&mut self, from: (Float, Float), to: (Float, Float)) {
+        let (from, to) = (onto_trackball(from), onto_trackball(to));
+        if from == to {
+            return;
+        }
+        // Turning the scene one way is turning the camera the other way about the target
+        let turn = Rotor::from_rotation_between_vectors(from, to);
+        self.goal.0 = self.goal.0 * turn.reversed();
+    }
+
+    /// Scales the distance to the target, so factors below one zoom in
+    pub fn zoom<F: Into<Float>>(&mut self, factor: F) {
+        self.goal.1 = (self.goal.1 * factor.into()).max(MIN_DISTANCE);
+    }
+}
+
+/// Lifts a point in normalized coordinates onto a sphere blended into a hyperbolic sheet,
+/// which keeps drags outside the unit circle smooth
+fn onto_trackball((x, y): (Float, Float)) -> Vector {
+    let half = Float::from(0.5);
+    let d2 = x * x + y * y;
+    let z = if d2 <= half {
+        (Float::ONE - d2).sqrt()
+    } else {
+        half / d2.sqrt()
+    };
+    Vector::new(x, y, z).normalized()
+}
+
+impl Camera for ArcballCamera {
+    fn update(&mut self, dt: Float) {
+        let weight = smoothing_weight(self.smoothing, dt);
+        let (rotation, distance) = self.current;
+        self.current = (
+            rotation.slerp(&self.goal.0, weight),
+            lerp(distance, self.goal.1, weight),
+        );
+    }
+
+    fn snap(&mut self) {
+        self.current = self.goal;
+    }
+
+    fn eye(&self) -> Point {
+        let (rotation, distance) = self.current;
+        self.target + Vector::unit_z().rotated_by(&rotation) * distance
+    }
+
+    fn view_matrix(&self) -> Matrix {
+        let up = Vector::unit_y().rotated_by(&self.current.0);
+        Matrix::look_at(self.eye(), self.target, up).transposed()
+    }
+}
+
+#[cfg(test)]
+mod camera_tests {
+    use super::*;
+    use crate::frustum::Containment;
+    use crate::frustum::Frustum;
+    use crate::matrix::ClipSpace;
+    use crate::traits::Approximately;
+    use crate::viewport::Viewport;
+
+    const EPSILON: Float = 1e-9;
+
+    /// Transforms a world space point into view space
+    fn to_view(camera: &impl Camera, point: &Point) -> Point {
+        &camera.view_matrix() * point
+    }
+
+    #[test]
+    fn orbit_placement() {
+        let target = Point::new(1.0, 2.0, 3.0);
+        let mut camera = OrbitCamera::new(target, 4.0, 0.0, 0.0);
+        assert!(camera
+            .eye()
+            .approximately(&Point::new(1.0, 2.0, 7.0), EPSILON));
+        assert!(to_view(&camera, &target).approximately(&Point::new(0.0, 0.0, -4.0), EPSILON));
+
+        camera.rotate(PI / 2.0, PI / 4.0);
+        camera.snap();
+        let height = 4.0 * (PI / 4.0).sin();
+        assert!(camera
+            .eye()
+            .approximately(&Point::new(1.0 + height, 2.0 + height, 3.0), EPSILON));
+        assert!(to_view(&camera, &target).approximately(&Point::new(0.0, 0.0, -4.0), EPSILON));
+
+        // The pitch stops short of the pole
+        camera.rotate(0.0, PI);
+        camera.snap();
+        assert!(camera.angles().1 < PI / 2.0);
+    }
+
+    #[test]
+    fn view_projection_feeds_frustum_and_viewport() {
+        let target = Point::new(1.0, 2.0, 3.0);
+        let camera = OrbitCamera::new(target, 4.0, 0.5, 0.3);
+        let viewport = Viewport::new(0.0, 0.0, 800.0, 600.0);
+        let projection =
+            Matrix::perspective_fov(1.0, viewport.aspect(), 0.1, 100.0, ClipSpace::VULKAN);
+
+        let frustum = Frustum::from_matrix(&camera.view_projection(&projection), ClipSpace::VULKAN);
+        assert_eq!(frustum.classify_point(&target), Containment::Inside);
+        assert_eq!(frustum.classify_point(&camera.eye()), Containment::Outside);
+
+        let pixel = viewport
+            .project(&target, &camera.view_matrix(), &projection)
+            .unwrap();
+        assert!(pixel.x.approximately(400.0, 1e-9));
+        assert!(pixel.y.approximately(300.0, 1e-9));
+    }
+
+    #[test]
+    fn orbit_zoom_and_pan() {
+        let mut camera = OrbitCamera::new(Point::origin(), 10.0, 0.0, 0.0);
+        camera.zoom(0.5);
+        camera.pan(1.0, 2.0);
+        camera.snap();
+        assert!(camera.distance().approximately(5.0, EPSILON));
+        assert!(camera
+            .target()
+            .approximately(&Point::new(1.0, 2.0, 0.0), EPSILON));
+    }
+
+    #[test]
+    fn smoothing_follows_goal() {
+        let smoothing = 0.2;
+        let mut camera =
+            OrbitCamera::new(Point::origin(), 10.0, 0.0, 0.0).with_smoothing(smoothing);
+        camera.zoom(0.5);
+        assert!(camera.distance().approximately(10.0, EPSILON));
+
+        // One half-life of the exponential decay covers half of the way
+        camera.update(smoothing * (2.0 as Float).ln());
+        assert!(camera.distance().approximately(7.5, EPSILON));
+
+        for _ in 0..200 {
+            camera.update(0.05);
+        }
+        assert!(camera.distance().approximately(5.0, 1e-6));
+    }
+
+    #[test]
+    fn fly_orientation() {
+        let mut camera = FlyCamera::new(Point::new(0.0, 1.0, 0.0), 0.0, 0.0);
+        assert!(camera.forward().approximately(-Vector::unit_z(), EPSILON));
+
+        // Yawing left turns toward -X, then pitching up tilts toward +Y
+        camera.rotate(PI / 2.0, 0.0);
+        camera.snap();
+        assert!(camera.forward().approximately(-Vector::unit_x(), EPSILON));
+        assert!(camera.right().approximately(-Vector::unit_z(), EPSILON));
+
+        camera.rotate(0.0, PI / 4.0);
+        camera.snap();
+        let diagonal = Vector::new(-1.0, 1.0, 0.0).normalized();
+        assert!(camera.forward().approximately(diagonal, EPSILON));
+
+        let ahead = camera.eye() + camera.forward() * 3.0;
+        assert!(to_view(&camera, &ahead).approximately(&Point::new(0.0, 0.0, -3.0), EPSILON));
+    }
+
+    #[test]
+    fn fly_translation() {
+        let mut camera = FlyCamera::new(Point::origin(), PI / 2.0, 0.0);
+        camera.translate(2.0, 1.0, 0.5);
+        camera.snap();
+        assert!(camera
+            .eye()
+            .approximately(&Point::new(-2.0, 0.5, -1.0), EPSILON));
+    }
+
+    #[test]
+    fn arcball_drag() {
+        let target = Point::new(0.0, 0.0, -2.0);
+        let mut camera = ArcballCamera::new(target, 5.0);
+        assert!(camera
+            .eye()
+            .approximately(&Point::new(0.0, 0.0, 3.0), EPSILON));
+
+        // Dragging right turns the front of the scene to the right
+        camera.drag((0.0, 0.0), (0.5, 0.0));
+        camera.snap();
+        let front = Point::new(0.0, 0.0, -1.0);
+        assert!(to_view(&camera, &front).x > 0.0);
+        assert!(to_view(&camera, &target).approximately(&Point::new(0.0, 0.0, -5.0), EPSILON));
+
+        // Dragging back undoes it
+        camera.drag((0.5, 0.0), (0.0, 0.0));
+        camera.snap();
+        assert!(camera
+            .eye()
+            .approximately(&Point::new(0.0, 0.0, 3.0), EPSILON));
+    }
+
+    #[test]
+    fn arcball_smoothing() {
+        let mut camera = ArcballCamera::new(Point::origin(), 1.0).with_smoothing(0.1);
+        camera.drag((0.0, -0.5), (0.0, 0.5));
+        camera.update(0.01);
+        let partial = camera.orientation().angle();
+        assert!(partial > 0.0);
+        for _ in 0..200 {
+            camera.update(0.05);
+        }
+        assert!(camera.orientation().angle() > partial);
+        assert!(camera.orientation().magnitude().approximately(1.0, EPSILON));
+    }
+}
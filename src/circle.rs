@@ -1,24 +1,462 @@
+//!
+//! Oriented circles in 3D space
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::constant::PI;
+use crate::line::Line;
+use crate::plane::Plane;
+use crate::ray::Ray;
+use crate::ray::RayHit;
+use crate::traits::Approximately;
+use crate::traits::Distance;
+use crate::traits::FloatExt;
+use crate::traits::FromLossy;
+use crate::traits::Intersects;
+use crate::traits::Parallel;
 use crate::Float;
 use crate::Point;
 use crate::Vector;
 
+/// Tolerance used when deciding whether points lie on the circle, relative to its radius
+const TOLERANCE: f64 = 1e-9;
+
 /// An oriented circle in 3D space
+///
+/// The circle lies in the plane through its center perpendicular to its normal. Angles
+/// around the circle are measured counter-clockwise about the normal, starting from the
+/// direction given by [Vector::orthogonal] of the normal
+#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq)]
 pub struct Circle {
     center: Point,
-    frame: Vector,
+    normal: Vector,
+    radius: Float,
 }
 
 impl Circle {
+    /// Constructs a new [Circle], normalizing `normal`
     pub fn new(center: Point, normal: Vector, radius: Float) -> Self {
-        let frame = normal * radius;
-        Self { center, frame }
+        Self {
+            center,
+            normal: normal.normalized(),
+            radius: radius.abs(),
+        }
     }
 
     pub fn radius(&self) -> Float {
-        self.frame.length()
+        self.radius
     }
 
     pub fn center(&self) -> Point {
         self.center
     }
+
+    /// The unit normal of the plane the circle lies in
+    pub fn normal(&self) -> Vector {
+        self.normal
+    }
+
+    /// The plane the circle lies in
+    pub fn plane(&self) -> Plane {
+        Plane::new(self.normal, self.normal.dot(&self.center.as_vector()))
+    }
+
+    pub fn circumference(&self) -> Float {
+        Float::from(2.0) * PI * self.radius
+    }
+
+    pub fn area(&self) -> Float {
+        PI * self.radius * self.radius
+    }
+
+    /// Two unit vectors spanning the plane of the circle, such that `u × v` is the normal
+    fn basis(&self) -> (Vector, Vector) {
+        let u = self.normal.orthogonal().normalized();
+        let v = self.normal.cross(&u);
+        (u, v)
+    }
+
+    /// Returns the point on the circle at `angle` radians
+    pub fn point_at_angle<F: Into<Float>>(&self, angle: F) -> Point {
+        let angle = angle.into();
+        let (u, v) = self.basis();
+        self.center + (u * angle.cos() + v * angle.sin()) * self.radius
+    }
+
+    /// Returns `count` points evenly spaced around the circle, the first at angle zero
+    pub fn sample(&self, count: usize) -> Vec<Point> {
+        let step = Float::from(2.0) * PI / Float::from_lossy(count as i64);
+        (0..count)
+            .map(|i| self.point_at_angle(step * Float::from_lossy(i as i64)))
+            .collect()
+    }
+
+    /// Returns the point on the circle which is closest to `point`
+    ///
+    /// Every point on the circle is equally close to points along its axis, in which
+    /// case the point at angle zero is returned
+    pub fn closest_point(&self, point: &Point) -> Point {
+        let offset = point - self.center;
+        let in_plane = offset - self.normal * offset.dot(&self.normal);
+        if in_plane.length_sq() == Float::ZERO {
+            return self.point_at_angle(Float::ZERO);
+        }
+        self.center + in_plane.normalized() * self.radius
+    }
+
+    /// Test whether `point` lies on the flat disk bounded by the circle, within `epsilon`
+    /// of its plane
+    pub fn contains(&self, point: &Point, epsilon: Float) -> bool {
+        let offset = point - self.center;
+        let height = offset.dot(&self.normal);
+        let in_plane = offset - self.normal * height;
+        height.abs() <= epsilon && in_plane.length() <= self.radius + epsilon
+    }
+
+    /// Test whether `point` lies on the circle itself
+    fn on_circle(&self, point: &Point) -> bool {
+        let tolerance = self.tolerance();
+        let offset = point - self.center;
+        offset.dot(&self.normal).abs() <= tolerance
+            && (offset.length() - self.radius).abs() <= tolerance
+    }
+
+    fn tolerance(&self) -> Float {
+        Float::from(TOLERANCE) * Float::max(self.radius, Float::ONE)
+    }
+
+    /// The points where a line lying in the plane of the circle crosses it
+    fn coplanar_line_intersection(&self, line: &Line) -> Option<(Point, Point)> {
+        let direction = line.direction.normalized();
+        let foot = line.origin + direction * (self.center - line.origin).dot(&direction);
+        let h_sq = foot.distance_to_sq(&self.center);
+        let r_sq = self.radius * self.radius;
+        if h_sq > r_sq + self.tolerance() * self.radius {
+            return None;
+        }
+
+        let half_chord = Float::max(r_sq - h_sq, Float::ZERO).sqrt();
+        Some((foot - direction * half_chord, foot + direction * half_chord))
+    }
+}
+
+impl Approximately for Circle {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        self.center.approximately(&other.center, epsilon)
+            && self.normal.approximately(other.normal, epsilon)
+            && self.radius.approximately(other.radius, epsilon)
+    }
+}
+
+impl Distance<Point> for Circle {
+    /// The squared distance from the point to the nearest point on the circle
+    fn distance_to_sq(&self, other: &Point) -> Float {
+        self.closest_point(other).distance_to_sq(other)
+    }
+}
+
+impl Intersects<Plane> for Circle {
+    /// The points where the circle crosses the plane. A circle touching the plane at a
+    /// single point gives that point twice
+    ///
+    /// A circle lying in the plane shares all of its points with it, so although they
+    /// do intersect, `None` is returned
+    type Intersection = Option<(Point, Point)>;
+
+    fn interesects(&self, other: &Plane) -> bool {
+        let plane = self.plane();
+        if plane.parallel(other) {
+            return other
+                .distance_to(self.center)
+                .abs()
+                .approximately(Float::ZERO, self.tolerance());
+        }
+        self.intersection(other).is_some()
+    }
+
+    fn intersection(&self, other: &Plane) -> Self::Intersection {
+        let line = self.plane().intersection(other)?;
+        self.coplanar_line_intersection(&line)
+    }
+}
+
+impl Intersects<Line> for Circle {
+    /// The points where the line crosses the circle. A line meeting the circle at a single
+    /// point, whether tangent to it or passing through its plane, gives that point twice
+    type Intersection = Option<(Point, Point)>;
+
+    fn interesects(&self, other: &Line) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    fn intersection(&self, other: &Line) -> Self::Intersection {
+        let direction = other.direction.normalized();
+        let denom = self.normal.dot(&direction);
+        if denom.abs() <= Float::from(TOLERANCE) {
+            let height = (other.origin - self.center).dot(&self.normal);
+            if height.abs() > self.tolerance() {
+                return None;
+            }
+            return self.coplanar_line_intersection(other);
+        }
+
+        let t = (self.center - other.origin).dot(&self.normal) / denom;
+        let point = other.origin + direction * t;
+        self.on_circle(&point).then_some((point, point))
+    }
+}
+
+impl Intersects for Circle {
+    /// The points where two circles cross. Circles touching at a single point give that
+    /// point twice
+    ///
+    /// Coincident circles share all of their points, so although they do intersect,
+    /// `None` is returned
+    type Intersection = Option<(Point, Point)>;
+
+    fn interesects(&self, other: &Self) -> bool {
+        self.approximately(*other, self.tolerance()) || self.intersection(other).is_some()
+    }
+
+    fn intersection(&self, other: &Self) -> Self::Intersection {
+        let tolerance = Float::max(self.tolerance(), other.tolerance());
+        let parallel = self.normal.cross(&other.normal).length() <= Float::from(TOLERANCE);
+        let coplanar =
+            parallel && (other.center - self.center).dot(&self.normal).abs() <= tolerance;
+
+        if coplanar {
+            let offset = other.center - self.center;
+            let d = offset.length();
+            if d <= tolerance
+                || d > self.radius + other.radius + tolerance
+                || d < (self.radius - other.radius).abs() - tolerance
+            {
+                return None;
+            }
+
+            let direction = offset / d;
+            let a = (d * d + self.radius * self.radius - other.radius * other.radius)
+                / (Float::from(2.0) * d);
+            let h = Float::max(self.radius * self.radius - a * a, Float::ZERO).sqrt();
+            let midpoint = self.center + direction * a;
+            let across = self.normal.cross(&direction);
+            return Some((midpoint - across * h, midpoint + across * h));
+        }
+
+        if parallel {
+            return None;
+        }
+
+        // Both circles can only meet on the line their planes share
+        let (p, q) = self.intersection(&other.plane())?;
+        match (other.on_circle(&p), other.on_circle(&q)) {
+            (true, true) => Some((p, q)),
+            (true, false) => Some((p, p)),
+            (false, true) => Some((q, q)),
+            (false, false) => None,
+        }
+    }
+}
+
+impl Intersects<Ray> for Circle {
+    type Intersection = Option<RayHit>;
+
+    fn interesects(&self, other: &Ray) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Casts the ray against the flat disk bounded by the circle
+    fn intersection(&self, other: &Ray) -> Self::Intersection {
+        let denom = self.normal.dot(&other.direction);
+        if denom == Float::ZERO {
+            return None;
+        }
+
+        let t = (self.center - other.origin).dot(&self.normal) / denom;
+        if !other.in_range(t) {
+            return None;
+        }
+
+        let hit = other.facing_hit(t, self.normal);
+        (hit.point.distance_to_sq(&self.center) <= self.radius * self.radius).then_some(hit)
+    }
+}
+
+#[cfg(test)]
+mod circle_tests {
+    use super::*;
+
+    const EPSILON: Float = 1e-9;
+
+    fn unit_xy() -> Circle {
+        Circle::new(Point::origin(), Vector::new(0.0, 0.0, 2.0), 1.0)
+    }
+
+    fn assert_points(actual: Option<(Point, Point)>, a: Point, b: Point) {
+        let (p, q) = actual.expect("expected an intersection");
+        let matches = |x: &Point, y: &Point| x.approximately(y, EPSILON);
+        assert!(
+            (matches(&p, &a) && matches(&q, &b)) || (matches(&p, &b) && matches(&q, &a)),
+            "{p} {q}"
+        );
+    }
+
+    #[test]
+    fn accessors() {
+        let circle = unit_xy();
+        assert!(circle.normal().approximately(Vector::unit_z(), EPSILON));
+        assert!(circle.radius().approximately(1.0, EPSILON));
+        assert!(circle.area().approximately(PI, EPSILON));
+        assert!(circle.circumference().approximately(2.0 * PI, EPSILON));
+    }
+
+    #[test]
+    fn points_around_the_circle() {
+        let circle = Circle::new(Point::new(1.0, 2.0, 3.0), Vector::new(1.0, 1.0, 0.0), 2.0);
+        let start = circle.point_at_angle(0.0);
+        let quarter = circle.point_at_angle(PI / 2.0);
+
+        for point in [start, quarter] {
+            assert!(point
+                .distance_to(&circle.center())
+                .approximately(2.0, EPSILON));
+            assert!((point - circle.center())
+                .dot(&circle.normal())
+                .approximately(0.0, EPSILON));
+        }
+
+        // Counter-clockwise about the normal
+        let turn = (start - circle.center()).cross(&(quarter - circle.center()));
+        assert!(turn.normalized().approximately(circle.normal(), EPSILON));
+
+        let samples = circle.sample(8);
+        assert_eq!(samples.len(), 8);
+        assert!(samples[0].approximately(&start, EPSILON));
+        assert!(samples[2].approximately(&quarter, EPSILON));
+    }
+
+    #[test]
+    fn closest_point_and_containment() {
+        let circle = unit_xy();
+        let closest = circle.closest_point(&Point::new(3.0, 0.0, 4.0));
+        assert!(closest.approximately(&Point::new(1.0, 0.0, 0.0), EPSILON));
+        assert!(circle
+            .distance_to(&Point::new(3.0, 0.0, 0.0))
+            .approximately(2.0, EPSILON));
+        assert!(circle
+            .distance_to(&Point::new(0.0, 0.0, 1.0))
+            .approximately(Float::sqrt(2.0), EPSILON));
+
+        assert!(circle.contains(&Point::new(0.5, 0.5, 0.0), EPSILON));
+        assert!(!circle.contains(&Point::new(0.8, 0.8, 0.0), EPSILON));
+        assert!(!circle.contains(&Point::new(0.1, 0.1, 0.5), EPSILON));
+    }
+
+    #[test]
+    fn sphere_intersection_is_usable() {
+        use crate::sphere::Sphere;
+        let a = Sphere::new(Point::origin(), 1.0);
+        let b = Sphere::new(Point::new(1.0, 0.0, 0.0), 1.0);
+        let circle = a.intersection(&b).unwrap();
+        assert!(circle
+            .center()
+            .approximately(&Point::new(0.5, 0.0, 0.0), EPSILON));
+        assert!(circle.normal().approximately(Vector::unit_x(), EPSILON));
+        assert!(circle.radius().approximately(Float::sqrt(0.75), EPSILON));
+    }
+
+    #[test]
+    fn plane_intersection() {
+        let circle = unit_xy();
+        let crossing = Plane::new(Vector::unit_x(), 0.5);
+        let half = Float::sqrt(0.75);
+        assert_points(
+            circle.intersection(&crossing),
+            Point::new(0.5, -half, 0.0),
+            Point::new(0.5, half, 0.0),
+        );
+
+        let tangent = Plane::new(Vector::unit_y(), 1.0);
+        let point = Point::new(0.0, 1.0, 0.0);
+        assert_points(circle.intersection(&tangent), point, point);
+
+        assert!(!circle.interesects(&Plane::new(Vector::unit_x(), 2.0)));
+        assert!(!circle.interesects(&Plane::new(Vector::unit_z(), 1.0)));
+
+        let containing = Plane::new(Vector::unit_z(), 0.0);
+        assert!(circle.interesects(&containing));
+        assert!(circle.intersection(&containing).is_none());
+    }
+
+    #[test]
+    fn line_intersection() {
+        let circle = unit_xy();
+        let chord = Line::new(Point::new(-5.0, 0.0, 0.0), Vector::unit_x());
+        assert_points(
+            circle.intersection(&chord),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+        );
+
+        let piercing = Line::new(Point::new(0.0, 1.0, 5.0), Vector::unit_z());
+        let point = Point::new(0.0, 1.0, 0.0);
+        assert_points(circle.intersection(&piercing), point, point);
+
+        let through_disk = Line::new(Point::new(0.0, 0.5, 5.0), Vector::unit_z());
+        assert!(!circle.interesects(&through_disk));
+
+        let above = Line::new(Point::new(-5.0, 0.0, 1.0), Vector::unit_x());
+        assert!(!circle.interesects(&above));
+    }
+
+    #[test]
+    fn coplanar_circle_intersection() {
+        let a = unit_xy();
+        let b = Circle::new(Point::new(1.0, 0.0, 0.0), Vector::unit_z(), 1.0);
+        let half = Float::sqrt(0.75);
+        assert_points(
+            a.intersection(&b),
+            Point::new(0.5, -half, 0.0),
+            Point::new(0.5, half, 0.0),
+        );
+
+        let touching = Circle::new(Point::new(2.0, 0.0, 0.0), -Vector::unit_z(), 1.0);
+        let point = Point::new(1.0, 0.0, 0.0);
+        assert_points(a.intersection(&touching), point, point);
+
+        let inside = Circle::new(Point::new(0.1, 0.0, 0.0), Vector::unit_z(), 0.5);
+        assert!(!a.interesects(&inside));
+
+        assert!(a.interesects(&unit_xy()));
+        assert!(a.intersection(&unit_xy()).is_none());
+    }
+
+    #[test]
+    fn non_coplanar_circle_intersection() {
+        let a = unit_xy();
+        // A circle in the xz plane which meets the plane of the first inside of it
+        let linked = Circle::new(Point::new(1.0, 0.0, 0.0), Vector::unit_y(), 0.5);
+        assert!(a.intersection(&linked).is_none());
+
+        // A circle in the xz plane crossing the first at two points
+        let crossing = Circle::new(Point::origin(), Vector::unit_y(), 1.0);
+        assert_points(
+            a.intersection(&crossing),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+        );
+
+        // Touching at a single point
+        let touching = Circle::new(Point::new(0.0, 1.0, 1.0), Vector::unit_x(), 1.0);
+        assert_points(
+            a.intersection(&touching),
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+        );
+
+        let parallel = Circle::new(Point::new(0.0, 0.0, 1.0), Vector::unit_z(), 1.0);
+        assert!(!a.interesects(&parallel));
+    }
 }
@@ -1,6 +1,6 @@
 //! Various useful constants
 
-#[allow(clippy::excessive_precision)]
+#[allow(clippy::excessive_precision, clippy::approx_constant)]
 pub mod precise {
     /// Default epsilon used for comparisons
     pub const EPSILON: f64 = 1e-6;
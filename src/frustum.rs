@@ -0,0 +1,382 @@
+//!
+//! View frusta
+//!
+//! A [Frustum] is the volume a camera can see, bounded by six [Plane]s with their normals
+//! pointing inward. It is extracted from a view-projection [Matrix] and used to cull shapes
+//! which can not appear on screen
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::aabb::Aabb;
+use crate::matrix::ClipSpace;
+use crate::matrix::DepthRange;
+use crate::matrix::Matrix;
+use crate::plane::Plane;
+use crate::sphere::Sphere;
+use crate::traits::FloatExt;
+use crate::traits::FromLossy;
+use crate::traits::Zero;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+
+/// How a shape lies relative to a [Frustum]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Containment {
+    /// Entirely inside the frustum
+    Inside,
+    /// Entirely outside the frustum
+    Outside,
+    /// Crossing at least one of the frustum planes
+    Intersecting,
+}
+
+/// A view frustum bounded by six [Plane]s whose normals point into the frustum
+///
+/// `bottom` and `top` bound normalized device coordinate -Y and +Y, which are swapped on
+/// screen for a [ClipSpace] with `y_flip` set
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Frustum {
+    pub left: Plane,
+    pub right: Plane,
+    pub bottom: Plane,
+    pub top: Plane,
+    pub near: Plane,
+    pub far: Plane,
+}
+
+impl Frustum {
+    /// Extracts the [Frustum] of a view-projection [Matrix] which maps world space points to
+    /// clip space as `&Matrix * &Point` does, targeting the clip space convention `clip`
+    ///
+    /// With a view which transforms column vectors, as [crate::camera::Camera::view_matrix]
+    /// or the transpose of [Matrix::look_at] does, this is `&projection * &view`, see
+    /// [crate::camera::Camera::view_projection]. Only the depth range and reversed-Z of `clip`
+    /// matter. A projection with an infinite far plane gives a `far` plane that culls nothing
+    /// ```
+    /// # use integrator::frustum::*;
+    /// # use integrator::matrix::*;
+    /// # use integrator::Point;
+    /// let projection = Matrix::perspective_fov(1.0, 1.0, 0.1, 100.0, ClipSpace::VULKAN);
+    /// let frustum = Frustum::from_matrix(&projection, ClipSpace::VULKAN);
+    /// assert_eq!(frustum.classify_point(&Point::new(0.0, 0.0, -10.0)), Containment::Inside);
+    /// assert_eq!(frustum.classify_point(&Point::new(0.0, 0.0, 10.0)), Containment::Outside);
+    /// ```
+    pub fn from_matrix(view_projection: &Matrix, clip: ClipSpace) -> Self {
+        let row = |i: usize| view_projection[i];
+        let combine = |a: [Float; 4], b: [Float; 4], sign: Float| -> [Float; 4] {
+            std::array::from_fn(|i| a[i] + sign * b[i])
+        };
+        let one = Float::ONE;
+
+        // Gribb and Hartmann: a clip space point is inside when -w <= x <= w, -w <= y <= w
+        // and the depth range bounds z, each of which is a plane in world space
+        let (w, x, y, z) = (row(3), row(0), row(1), row(2));
+        let low_depth = match clip.depth {
+            DepthRange::ZeroToOne => z,
+            DepthRange::NegativeOneToOne => combine(w, z, one),
+        };
+        let high_depth = combine(w, z, -one);
+        let (near, far) = if clip.reversed_z {
+            (high_depth, low_depth)
+        } else {
+            (low_depth, high_depth)
+        };
+
+        Self {
+            left: plane_from_coefficients(combine(w, x, one)),
+            right: plane_from_coefficients(combine(w, x, -one)),
+            bottom: plane_from_coefficients(combine(w, y, one)),
+            top: plane_from_coefficients(combine(w, y, -one)),
+            near: plane_from_coefficients(near),
+            far: plane_from_coefficients(far),
+        }
+    }
+
+    /// The six planes in the order left, right, bottom, top, near, far
+    pub fn planes(&self) -> [&Plane; 6] {
+        [
+            &self.left,
+            &self.right,
+            &self.bottom,
+            &self.top,
+            &self.near,
+            &self.far,
+        ]
+    }
+
+    /// Classifies a [Point] as [Containment::Inside] or [Containment::Outside]
+    ///
+    /// Points on the boundary are inside
+    pub fn classify_point(&self, point: &Point) -> Containment {
+        let inside = self
+            .planes()
+            .iter()
+            .all(|plane| plane.distance_to(*point) >= Float::ZERO);
+        if inside {
+            Containment::Inside
+        } else {
+            Containment::Outside
+        }
+    }
+
+    /// Classifies a [Sphere] against the frustum
+    ///
+    /// Like all plane based culling this is conservative: a sphere near a corner of the
+    /// frustum may be reported as intersecting while lying just outside
+    pub fn classify_sphere(&self, sphere: &Sphere) -> Containment {
+        self.classify_extent(sphere.center, |_| sphere.radius)
+    }
+
+    /// Classifies an [Aabb] against the frustum
+    ///
+    /// Conservative in the same way as [Frustum::classify_sphere]
+    pub fn classify_aabb(&self, aabb: &Aabb) -> Containment {
+        let extents = aabb.half_extents();
+        self.classify_extent(aabb.center(), |n| {
+            extents.x * n.x.abs() + extents.y * n.y.abs() + extents.z * n.z.abs()
+        })
+    }
+
+    /// Classifies a shape from its center and its extent along each plane normal
+    fn classify_extent<E: Fn(&Vector) -> Float>(&self, center: Point, extent: E) -> Containment {
+        let mut containment = Containment::Inside;
+        for plane in self.planes() {
+            let distance = plane.distance_to(center);
+            let radius = extent(&plane.norm);
+            if distance < -radius {
+                return Containment::Outside;
+            }
+            if distance < radius {
+                containment = Containment::Intersecting;
+            }
+        }
+        containment
+    }
+
+    /// The eight corners of the frustum
+    ///
+    /// Corner `i` lies on the `right`, `top` and `far` planes where bits 0, 1 and 2 of `i`
+    /// are set respectively, and on the `left`, `bottom` and `near` planes otherwise, matching
+    /// [Aabb::corners]. Returns `None` if the planes do not meet in points, as for a
+    /// projection with an infinite far plane
+    pub fn corners(&self) -> Option<[Point; 8]> {
+        let mut corners = [Point::origin(); 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let x = if i & 1 == 0 { &self.left } else { &self.right };
+            let y = if i & 2 == 0 { &self.bottom } else { &self.top };
+            let z = if i & 4 == 0 { &self.near } else { &self.far };
+            *corner = intersect_planes(x, y, z)?;
+        }
+        Some(corners)
+    }
+
+    /// Returns the part of this frustum between `start` and `end`, measured from the near
+    /// plane along its normal
+    pub fn slice(&self, start: Float, end: Float) -> Self {
+        let forward = self.near.norm;
+        let offset = self.near.dist;
+        Self {
+            near: Plane {
+                norm: forward,
+                dist: offset + start,
+            },
+            far: Plane {
+                norm: -forward,
+                dist: -(offset + end),
+            },
+            ..*self
+        }
+    }
+
+    /// Splits this frustum into consecutive sub-frusta, for example the cascades of a
+    /// cascaded shadow map
+    ///
+    /// `distances` are the boundaries between the sub-frusta measured from the near plane,
+    /// see [Frustum::cascade_distances], so `n + 1` distances give `n` sub-frusta
+    pub fn split(&self, distances: &[Float]) -> Vec<Self> {
+        distances
+            .windows(2)
+            .map(|pair| self.slice(pair[0], pair[1]))
+            .collect()
+    }
+
+    /// Computes the `count + 1` boundaries of `count` cascades between the `near` and `far`
+    /// view distances, measured from the near plane as [Frustum::split] expects
+    ///
+    /// `lambda` blends between evenly spaced (0) and logarithmically spaced (1) cascades,
+    /// the practical split scheme of parallel-split shadow maps
+    pub fn cascade_distances(near: Float, far: Float, count: usize, lambda: Float) -> Vec<Float> {
+        (0..=count)
+            .map(|i| {
+                let fraction = Float::from_lossy(i as i64) / Float::from_lossy(count.max(1) as i64);
+                let logarithmic = near * (far / near).powf(fraction);
+                let uniform = near + (far - near) * fraction;
+                lambda * logarithmic + (Float::ONE - lambda) * uniform - near
+            })
+            .collect()
+    }
+}
+
+/// Builds the [Plane] `ax + by + cz + d >= 0` with its normal pointing to the positive side
+///
+/// A degenerate plane, left by an infinite far plane, is placed at infinity so it culls nothing
+fn plane_from_coefficients([a, b, c, d]: [Float; 4]) -> Plane {
+    let normal = Vector::new(a, b, c);
+    let length = normal.length();
+    if length <= Float::EPSILON * d.abs() {
+        return Plane {
+            norm: Vector::zero(),
+            dist: -Float::INFINITY,
+        };
+    }
+    Plane {
+        norm: normal / length,
+        dist: -d / length,
+    }
+}
+
+/// Finds the single point where three planes meet
+fn intersect_planes(a: &Plane, b: &Plane, c: &Plane) -> Option<Point> {
+    let bc = b.norm.cross(&c.norm);
+    let denominator = a.norm.dot(&bc);
+    if denominator.abs() <= Float::EPSILON {
+        return None;
+    }
+    let numerator = bc * a.dist + c.norm.cross(&a.norm) * b.dist + a.norm.cross(&b.norm) * c.dist;
+    Some(Point::from(numerator / denominator))
+}
+
+#[cfg(test)]
+mod frustum_tests {
+    use super::*;
+    use crate::constant::precise::PI;
+    use crate::traits::Approximately;
+
+    const EPSILON: Float = 1e-9;
+
+    /// A camera at (0, 0, 5) looking toward the origin
+    fn frustum(clip: ClipSpace) -> Frustum {
+        let projection = Matrix::perspective_fov(PI / 2.0, 1.0, 1.0, 11.0, clip);
+        let eye = Point::new(0.0, 0.0, 5.0);
+        let view = Matrix::look_at(eye, Point::origin(), Vector::unit_y()).transposed();
+        Frustum::from_matrix(&(&projection * &view), clip)
+    }
+
+    #[test]
+    fn planes_face_inward() {
+        for clip in [
+            ClipSpace::VULKAN,
+            ClipSpace::OPENGL,
+            ClipSpace::WEBGPU.with_reversed_z(true),
+        ] {
+            let frustum = frustum(clip);
+            assert!(frustum.near.norm.approximately(-Vector::unit_z(), EPSILON));
+            assert!(frustum
+                .near
+                .distance_to(Point::new(0.0, 0.0, 4.0))
+                .approximately(0.0, EPSILON));
+            assert!(frustum
+                .far
+                .distance_to(Point::new(0.0, 0.0, -6.0))
+                .approximately(0.0, EPSILON));
+            assert!(frustum.right.norm.x < 0.0 && frustum.left.norm.x > 0.0);
+        }
+    }
+
+    #[test]
+    fn classify_points() {
+        let frustum = frustum(ClipSpace::VULKAN);
+        assert_eq!(
+            frustum.classify_point(&Point::origin()),
+            Containment::Inside
+        );
+        assert_eq!(
+            frustum.classify_point(&Point::new(0.0, 0.0, 4.5)),
+            Containment::Outside
+        );
+        assert_eq!(
+            frustum.classify_point(&Point::new(0.0, 0.0, -7.0)),
+            Containment::Outside
+        );
+        // The field of view is 90 degrees, so the sides are at 45 degrees
+        assert_eq!(
+            frustum.classify_point(&Point::new(4.9, 0.0, 0.0)),
+            Containment::Inside
+        );
+        assert_eq!(
+            frustum.classify_point(&Point::new(0.0, -5.1, 0.0)),
+            Containment::Outside
+        );
+    }
+
+    #[test]
+    fn classify_shapes() {
+        let frustum = frustum(ClipSpace::OPENGL);
+        let inside = Sphere::new(Point::origin(), 1.0);
+        let crossing = Sphere::new(Point::new(5.0, 0.0, 0.0), 1.0);
+        let outside = Sphere::new(Point::new(0.0, 0.0, 10.0), 1.0);
+        assert_eq!(frustum.classify_sphere(&inside), Containment::Inside);
+        assert_eq!(
+            frustum.classify_sphere(&crossing),
+            Containment::Intersecting
+        );
+        assert_eq!(frustum.classify_sphere(&outside), Containment::Outside);
+
+        let inside = Aabb::from_center_and_half_extents(Point::origin(), Vector::from(1.0));
+        let crossing = Aabb::new(Point::new(0.0, 0.0, -8.0), Point::new(1.0, 1.0, -5.0));
+        let outside = Aabb::new(Point::new(6.0, -1.0, 1.0), Point::new(7.0, 1.0, 2.0));
+        assert_eq!(frustum.classify_aabb(&inside), Containment::Inside);
+        assert_eq!(frustum.classify_aabb(&crossing), Containment::Intersecting);
+        assert_eq!(frustum.classify_aabb(&outside), Containment::Outside);
+    }
+
+    #[test]
+    fn corners() {
+        let corners = frustum(ClipSpace::WEBGPU).corners().unwrap();
+        assert!(corners[0].approximately(&Point::new(-1.0, -1.0, 4.0), EPSILON));
+        assert!(corners[3].approximately(&Point::new(1.0, 1.0, 4.0), EPSILON));
+        assert!(corners[4].approximately(&Point::new(-11.0, -11.0, -6.0), EPSILON));
+        assert!(corners[7].approximately(&Point::new(11.0, 11.0, -6.0), EPSILON));
+    }
+
+    #[test]
+    fn infinite_far_plane() {
+        let clip = ClipSpace::VULKAN.with_reversed_z(true);
+        let projection = Matrix::perspective_infinite(PI / 2.0, 1.0, 1.0, clip);
+        let frustum = Frustum::from_matrix(&projection, clip);
+        assert_eq!(
+            frustum.classify_point(&Point::new(0.0, 0.0, -1e12)),
+            Containment::Inside
+        );
+        assert_eq!(
+            frustum.classify_point(&Point::new(0.0, 0.0, -0.5)),
+            Containment::Outside
+        );
+        assert!(frustum.corners().is_none());
+    }
+
+    #[test]
+    fn split_into_cascades() {
+        let frustum = frustum(ClipSpace::WEBGPU);
+        let distances = Frustum::cascade_distances(1.0, 11.0, 2, 0.0);
+        assert_eq!(distances.len(), 3);
+        assert!(distances[1].approximately(5.0, EPSILON));
+        assert!(distances[2].approximately(10.0, EPSILON));
+
+        let cascades = frustum.split(&distances);
+        assert_eq!(cascades.len(), 2);
+        let first = cascades[0].corners().unwrap();
+        let second = cascades[1].corners().unwrap();
+        let whole = frustum.corners().unwrap();
+        assert!(first[0].approximately(&whole[0], EPSILON));
+        assert!(first[4].approximately(&second[0], EPSILON));
+        assert!(first[4].approximately(&Point::new(-6.0, -6.0, -1.0), EPSILON));
+        assert!(second[7].approximately(&whole[7], EPSILON));
+
+        let logarithmic = Frustum::cascade_distances(1.0, 100.0, 2, 1.0);
+        assert!(logarithmic[1].approximately(9.0, EPSILON));
+    }
+}
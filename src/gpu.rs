@@ -0,0 +1,348 @@
+//!
+//! GPU buffer layouts
+//!
+//! Writes values into `f32` byte buffers following the std140 and std430 layout rules of GLSL
+//! and SPIR-V, ready to upload as uniform or storage buffers. Bytes are in native endianness,
+//! matching the host the buffer is uploaded from
+//!
+//! Each type maps onto a GLSL type:
+//! - [Float] is a `float`
+//! - [Vector] and [Point] are a `vec3`
+//! - [Rotor] is a `vec4` quaternion `(x, y, z, w)`, with the scalar part in `w`
+//! - [Matrix] is a `mat4`, written column-major so that `M * v` in a shader transforms as
+//!   `&Matrix * &Point` does. Views from [crate::camera::Camera::view_matrix] are already in
+//!   this layout, while a [Matrix::look_at] view must be transposed first
+//!
+
+use crate::matrix::Matrix;
+use crate::rotor::Rotor;
+use crate::traits::FromLossy;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+
+/// The alignment of a `vec4`, which std140 also imposes on arrays and nested blocks
+const VEC4_ALIGNMENT: usize = 16;
+
+/// The memory layout rules a buffer is written with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// The layout of uniform buffers, which pads arrays and nested blocks to 16 bytes
+    Std140,
+    /// The tighter layout of storage buffers and push constants
+    Std430,
+}
+
+/// A type which can be written into a GPU buffer
+pub trait GpuData {
+    /// The base alignment in bytes
+    const ALIGNMENT: usize;
+
+    /// The size in bytes, without any trailing padding
+    const SIZE: usize;
+
+    /// Appends the `SIZE` bytes of this value to `out`
+    fn write(&self, out: &mut Vec<u8>);
+
+    /// Returns the bytes of this value on its own
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::SIZE);
+        self.write(&mut out);
+        out
+    }
+}
+
+/// Rounds `offset` up to the next multiple of `alignment`
+fn align_up(offset: usize, alignment: usize) -> usize {
+    offset.div_ceil(alignment) * alignment
+}
+
+fn write_f32(value: Float, out: &mut Vec<u8>) {
+    out.extend_from_slice(&f32::from_lossy(value).to_ne_bytes());
+}
+
+impl GpuData for Float {
+    const ALIGNMENT: usize = 4;
+    const SIZE: usize = 4;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        write_f32(*self, out);
+    }
+}
+
+impl GpuData for u32 {
+    const ALIGNMENT: usize = 4;
+    const SIZE: usize = 4;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl GpuData for i32 {
+    const ALIGNMENT: usize = 4;
+    const SIZE: usize = 4;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl GpuData for Vector {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 12;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        for component in [self.x, self.y, self.z] {
+            write_f32(component, out);
+        }
+    }
+}
+
+impl GpuData for Point {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 12;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        self.as_vector().write(out);
+    }
+}
+
+impl GpuData for Rotor {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 16;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        // The quaternion axis is dual to the plane, see [crate::bivec::Bivector::from_axis_vector]
+        for component in [-self.b.yz, self.b.xz, -self.b.xy, self.s] {
+            write_f32(component, out);
+        }
+    }
+}
+
+impl GpuData for Matrix {
+    const ALIGNMENT: usize = 16;
+    const SIZE: usize = 64;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        for col in 0..4 {
+            for component in self.col(col) {
+                write_f32(component, out);
+            }
+        }
+    }
+}
+
+/// Builds the bytes of a uniform or storage block member by member, inserting the padding
+/// the [Layout] requires
+///
+/// Members are pushed in declaration order. For the GLSL block
+/// `{ mat4 model; vec3 color; float intensity; }`:
+/// ```
+/// # use integrator::gpu::*;
+/// # use integrator::matrix::Matrix;
+/// # use integrator::Vector;
+/// let mut block = UniformBlock::new(Layout::Std140);
+/// block
+///     .push(&Matrix::identity())
+///     .push(&Vector::new(1.0, 0.5, 0.0))
+///     .push(&0.8);
+/// // The float packs into the padding after the vec3
+/// assert_eq!(block.finish().len(), 80);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniformBlock {
+    layout: Layout,
+    bytes: Vec<u8>,
+    alignment: usize,
+}
+
+impl UniformBlock {
+    pub fn new(layout: Layout) -> Self {
+        Self {
+            layout,
+            bytes: Vec::new(),
+            alignment: 1,
+        }
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// The offset in bytes just past the last member, before any padding for the next
+    pub fn offset(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// The base alignment of this block when nested in another block or array
+    pub fn alignment(&self) -> usize {
+        match self.layout {
+            Layout::Std140 => align_up(self.alignment, VEC4_ALIGNMENT),
+            Layout::Std430 => self.alignment,
+        }
+    }
+
+    /// Pads to `alignment` and records it as a member alignment
+    fn align_to(&mut self, alignment: usize) {
+        self.bytes.resize(align_up(self.bytes.len(), alignment), 0);
+        self.alignment = self.alignment.max(alignment);
+    }
+
+    /// Appends a member, returning the block for chaining
+    pub fn push<T: GpuData>(&mut self, value: &T) -> &mut Self {
+        self.align_to(T::ALIGNMENT);
+        value.write(&mut self.bytes);
+        self
+    }
+
+    /// Appends an array member, padding each element out to the array stride
+    ///
+    /// Under std140 the stride is rounded up to 16 bytes, so a `float[]` takes 16 bytes per
+    /// element. Under std430 it is the element size rounded up to its alignment
+    pub fn push_array<T: GpuData>(&mut self, values: &[T]) -> &mut Self {
+        let (alignment, stride) = match self.layout {
+            Layout::Std140 => (
+                align_up(T::ALIGNMENT, VEC4_ALIGNMENT),
+                align_up(T::SIZE, VEC4_ALIGNMENT),
+            ),
+            Layout::Std430 => (T::ALIGNMENT, align_up(T::SIZE, T::ALIGNMENT)),
+        };
+        self.align_to(alignment);
+        for value in values {
+            let start = self.bytes.len();
+            value.write(&mut self.bytes);
+            self.bytes.resize(start + stride, 0);
+        }
+        self
+    }
+
+    /// Appends a nested block, such as a struct member
+    ///
+    /// Panics if `block` was built with a different [Layout]
+    pub fn push_block(&mut self, block: &UniformBlock) -> &mut Self {
+        assert_eq!(
+            self.layout, block.layout,
+            "Nested blocks must share the layout of their parent"
+        );
+        self.align_to(block.alignment());
+        self.bytes.extend_from_slice(&block.finish());
+        self
+    }
+
+    /// The bytes written so far, without trailing padding
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the bytes of the whole block, padded out to its alignment
+    pub fn finish(&self) -> Vec<u8> {
+        let mut bytes = self.bytes.clone();
+        bytes.resize(align_up(bytes.len(), self.alignment()), 0);
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod gpu_tests {
+    use super::*;
+    use crate::bivec::Bivector;
+
+    fn floats(bytes: &[u8]) -> Vec<f32> {
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_ne_bytes(chunk.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn matrix_is_column_major() {
+        let matrix = Matrix::from([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let bytes = matrix.to_bytes();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(
+            floats(&bytes),
+            [
+                1.0, 5.0, 9.0, 13.0, 2.0, 6.0, 10.0, 14.0, 3.0, 7.0, 11.0, 15.0, 4.0, 8.0, 12.0,
+                16.0
+            ]
+        );
+    }
+
+    #[test]
+    fn rotor_is_quaternion() {
+        let rotor = Rotor::new(Bivector::new(0.1, 0.2, 0.3), 0.9);
+        assert_eq!(floats(&rotor.to_bytes()), [-0.3, 0.2, -0.1, 0.9]);
+
+        // A rotation about +Z has its quaternion axis along +Z
+        let about_z = Rotor::from_axis_angle(Vector::unit_z(), 1.0);
+        let quaternion = floats(&about_z.to_bytes());
+        assert!(quaternion[0].abs() < 1e-7 && quaternion[1].abs() < 1e-7);
+        assert!(quaternion[2] > 0.0);
+    }
+
+    #[test]
+    fn vec3_packing() {
+        // { vec3 a; float b; vec3 c; } packs b into the padding of a in both layouts
+        for layout in [Layout::Std140, Layout::Std430] {
+            let mut block = UniformBlock::new(layout);
+            block.push(&Vector::new(1.0, 2.0, 3.0)).push(&4.0);
+            assert_eq!(block.offset(), 16);
+            block.push(&Point::new(5.0, 6.0, 7.0));
+            assert_eq!(block.offset(), 28);
+            let bytes = block.finish();
+            assert_eq!(bytes.len(), 32);
+            assert_eq!(floats(&bytes), [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn array_strides() {
+        let values: [Float; 3] = [1.0, 2.0, 3.0];
+
+        let mut std140 = UniformBlock::new(Layout::Std140);
+        std140.push(&0.5).push_array(&values);
+        let bytes = std140.finish();
+        assert_eq!(bytes.len(), 64);
+        let padded = floats(&bytes);
+        assert_eq!((padded[4], padded[8], padded[12]), (1.0, 2.0, 3.0));
+
+        let mut std430 = UniformBlock::new(Layout::Std430);
+        std430.push(&0.5).push_array(&values);
+        assert_eq!(floats(&std430.finish()), [0.5, 1.0, 2.0, 3.0]);
+
+        // vec3 arrays have a 16 byte stride in both layouts
+        let vectors = [Vector::new(1.0, 2.0, 3.0), Vector::new(4.0, 5.0, 6.0)];
+        let mut std430 = UniformBlock::new(Layout::Std430);
+        std430.push_array(&vectors);
+        assert_eq!(std430.finish().len(), 32);
+    }
+
+    #[test]
+    fn nested_blocks() {
+        // struct Light { vec3 position; float radius; }
+        // { float count; Light light; uint flags; }
+        let mut light = UniformBlock::new(Layout::Std430);
+        light.push(&Point::new(1.0, 2.0, 3.0)).push(&4.0);
+
+        let mut block = UniformBlock::new(Layout::Std430);
+        block.push(&2.0).push_block(&light).push(&7u32);
+        assert_eq!(block.offset(), 36);
+        assert_eq!(block.finish().len(), 48);
+
+        // std140 rounds the alignment of small structs up to 16
+        let mut small = UniformBlock::new(Layout::Std140);
+        small.push(&1.0);
+        assert_eq!(small.alignment(), 16);
+        assert_eq!(small.finish().len(), 16);
+        let mut std430 = UniformBlock::new(Layout::Std430);
+        std430.push(&1.0);
+        assert_eq!(std430.alignment(), 4);
+        assert_eq!(std430.finish().len(), 4);
+    }
+}
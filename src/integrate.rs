@@ -1,6 +1,53 @@
 //!
 //! Integration
 //!
+//! Ordinary differential equations are solved by the steppers in [ode]. Each [Stepper]
+//! owns a derivative function and can be advanced one step at a time with `step(state, t, dt)`,
+//! which is usually what a game loop wants, or driven across an interval with `solve`
+//!
+//! Steppers are also usable through the [Integrator] trait, where `from` and `to` are
+//! `(time, state)` pairs and `func` maps a `(time, state)` pair to the derivative of the state
+//! at that time. Only the time component of `to` is used
+//!
+//! The adaptive methods in [adaptive] choose their own step sizes to meet a requested
+//! tolerance, measuring errors with [Norm]
+//!
+//! Long running conservative simulations should prefer the methods in [symplectic], which
+//! keep the energy error bounded
+//!
+//! Definite integrals of `Float -> Float` functions are computed by the methods in [quadrature],
+//! which implement [Integrator] with `Float` input and output
+//!
+//! Orientations are advanced on the manifold of unit rotors by the Lie group methods in
+//! [orientation]
+//!
+
+use std::ops::Add;
+use std::ops::Mul;
+
+use crate::traits::FromLossy;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+
+pub mod adaptive;
+pub mod ode;
+pub mod orientation;
+pub mod quadrature;
+pub mod symplectic;
+
+pub use adaptive::DormandPrince;
+pub use ode::Euler;
+pub use ode::RungeKutta4;
+pub use ode::SemiImplicitEuler;
+pub use orientation::CrouchGrossman3;
+pub use orientation::MuntheKaas4;
+pub use symplectic::Leapfrog;
+pub use symplectic::VelocityVerlet;
+pub use symplectic::Yoshida4;
+
+/// Number of steps taken by fixed-step methods when used through [Integrator::integrate]
+pub const DEFAULT_STEPS: usize = 1000;
 
 pub trait Integrator {
     type Input;
@@ -10,3 +57,161 @@ pub trait Integrator {
     where
         F: Fn(Self::Input) -> Self::Output;
 }
+
+/// A quantity that can be advanced through time by an integrator
+///
+/// The [State::Derivative] is the rate of change of the state. For [Float] and [Vector] this
+/// is the type itself, for a [Point] it is the [Vector] velocity
+pub trait State: Copy {
+    type Derivative: Copy + Add<Output = Self::Derivative> + Mul<Float, Output = Self::Derivative>;
+
+    /// Returns the state reached by moving along `derivative` for `dt`
+    fn advance(&self, derivative: &Self::Derivative, dt: Float) -> Self;
+}
+
+impl State for Float {
+    type Derivative = Float;
+
+    #[inline]
+    fn advance(&self, derivative: &Self::Derivative, dt: Float) -> Self {
+        *self + *derivative * dt
+    }
+}
+
+impl State for Vector {
+    type Derivative = Vector;
+
+    #[inline]
+    fn advance(&self, derivative: &Self::Derivative, dt: Float) -> Self {
+        self + derivative * dt
+    }
+}
+
+impl State for Point {
+    type Derivative = Vector;
+
+    #[inline]
+    fn advance(&self, derivative: &Self::Derivative, dt: Float) -> Self {
+        self + derivative * dt
+    }
+}
+
+/// A measure of the size of a quantity, used by adaptive methods to estimate errors
+pub trait Norm {
+    fn norm(&self) -> Float;
+}
+
+impl Norm for Float {
+    #[inline]
+    fn norm(&self) -> Float {
+        self.abs()
+    }
+}
+
+impl Norm for Vector {
+    #[inline]
+    fn norm(&self) -> Float {
+        self.length()
+    }
+}
+
+impl Norm for Point {
+    #[inline]
+    fn norm(&self) -> Float {
+        self.as_vector().length()
+    }
+}
+
+/// A second order state made of a position and its velocity
+///
+/// The derivative of a [Phase] is another [Phase] holding the velocity and the acceleration
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Phase<S: State> {
+    pub position: S,
+    pub velocity: S::Derivative,
+}
+
+impl<S: State> Phase<S> {
+    pub fn new(position: S, velocity: S::Derivative) -> Self {
+        Self { position, velocity }
+    }
+}
+
+impl<S> State for Phase<S>
+where
+    S: State,
+    S::Derivative: State<Derivative = S::Derivative>,
+{
+    type Derivative = Phase<S::Derivative>;
+
+    #[inline]
+    fn advance(&self, derivative: &Self::Derivative, dt: Float) -> Self {
+        Self {
+            position: self.position.advance(&derivative.position, dt),
+            velocity: self.velocity.advance(&derivative.velocity, dt),
+        }
+    }
+}
+
+impl<S> Norm for Phase<S>
+where
+    S: State + Norm,
+    S::Derivative: Norm,
+{
+    /// The larger of the position and velocity norms
+    fn norm(&self) -> Float {
+        Float::max(self.position.norm(), self.velocity.norm())
+    }
+}
+
+impl<S> Add for Phase<S>
+where
+    S: State + Add<Output = S>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            position: self.position + rhs.position,
+            velocity: self.velocity + rhs.velocity,
+        }
+    }
+}
+
+impl<S> Mul<Float> for Phase<S>
+where
+    S: State + Mul<Float, Output = S>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Float) -> Self::Output {
+        Self {
+            position: self.position * rhs,
+            velocity: self.velocity * rhs,
+        }
+    }
+}
+
+/// A method that advances a state through time in discrete steps
+pub trait Stepper<S> {
+    /// Advances `state` from time `t` to time `t + dt` in a single step
+    fn step(&self, state: S, t: Float, dt: Float) -> S;
+
+    /// Advances `state` from time `from` to time `to` in `steps` equal steps
+    fn solve(&self, state: S, from: Float, to: Float, steps: usize) -> S {
+        let dt = step_size(from, to, steps);
+        let mut state = state;
+        for i in 0..steps {
+            let t = from + dt * Float::from_lossy(i as i64);
+            state = self.step(state, t, dt);
+        }
+        state
+    }
+}
+
+/// Splits the interval `from..to` into `steps` equal steps, returning the step size
+#[inline]
+pub(crate) fn step_size(from: Float, to: Float, steps: usize) -> Float {
+    debug_assert!(steps > 0);
+    (to - from) / Float::from_lossy(steps as i64)
+}
@@ -0,0 +1,580 @@
+//!
+//! Adaptive step size ordinary differential equation solvers
+//!
+
+use std::marker::PhantomData;
+
+use crate::integrate::Integrator;
+use crate::integrate::Norm;
+use crate::integrate::State;
+use crate::traits::FloatExt;
+use crate::Float;
+
+/// Counters describing the work done by an adaptive solver
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Statistics {
+    /// Number of accepted steps
+    pub steps: usize,
+    /// Number of steps rejected by error control
+    pub rejected: usize,
+    /// Number of times the derivative function was evaluated
+    pub evaluations: usize,
+}
+
+/// The final state of an adaptive solve along with the work it took to get there
+#[derive(Debug, Clone, Copy)]
+pub struct Solution<S> {
+    pub state: S,
+    pub stats: Statistics,
+}
+
+/// The outcome of a single attempted adaptive step
+#[derive(Debug, Clone, Copy)]
+pub struct Attempt<S> {
+    /// The proposed state at `t + dt`
+    pub state: S,
+    /// The estimated local error divided by the requested tolerance
+    pub error: Float,
+    /// The suggested size of the next step, or of the retry if this step was rejected
+    pub next_dt: Float,
+}
+
+impl<S> Attempt<S> {
+    /// Whether the estimated error of this step is within tolerance
+    pub fn accepted(&self) -> bool {
+        self.error <= Float::ONE
+    }
+}
+
+/// Continuous output over the interval covered by an adaptive solve
+///
+/// Each accepted step stores a fourth order interpolant, allowing the solution to be sampled
+/// at any time between the start and end of the solve without taking any additional steps
+pub struct DenseOutput<S: State> {
+    segments: Vec<Segment<S>>,
+}
+
+struct Segment<S: State> {
+    t: Float,
+    dt: Float,
+    start: S,
+    coefficients: [S::Derivative; 4],
+}
+
+impl<S: State> Segment<S> {
+    fn sample(&self, t: Float) -> S {
+        let theta = (t - self.t) / self.dt;
+        let theta_1 = Float::ONE - theta;
+        let [c1, c2, c3, c4] = self.coefficients;
+        let delta = c1 + (c2 + (c3 + c4 * theta_1) * theta) * theta_1;
+        self.start.advance(&delta, theta)
+    }
+}
+
+impl<S: State> DenseOutput<S> {
+    /// The start and end times covered by this output
+    pub fn span(&self) -> Option<(Float, Float)> {
+        let first = self.segments.first()?;
+        let last = self.segments.last()?;
+        Some((first.t, last.t + last.dt))
+    }
+
+    /// Sample the solution at time `t`, returns [None] if `t` is outside of [DenseOutput::span]
+    pub fn sample(&self, t: Float) -> Option<S> {
+        let first = self.segments.first()?;
+        let direction = first.dt.signum();
+        if (t - first.t) * direction < Float::ZERO {
+            return None;
+        }
+
+        let index = self
+            .segments
+            .partition_point(|segment| (segment.t + segment.dt - t) * direction < Float::ZERO);
+        self.segments.get(index).map(|segment| segment.sample(t))
+    }
+}
+
+/// The Dormand-Prince 5(4) method
+///
+/// An explicit Runge-Kutta method that takes fifth order steps and uses an embedded fourth
+/// order solution to estimate the error of each step. Steps with an error larger than the
+/// requested tolerance are rejected and retried with a smaller step, while the step size grows
+/// again whenever the error allows it
+///
+/// ```
+/// # use integrator::{ Float, integrate::DormandPrince };
+/// let decay = DormandPrince::new(|_t: Float, y: &Float| -*y).with_tolerances(1e-9, 1e-9);
+/// let solution = decay.solve(1.0, 0.0, 1.0).unwrap();
+/// assert!((solution.state - (-1.0 as Float).exp()).abs() < 1e-8);
+/// ```
+pub struct DormandPrince<S: State, F = fn(Float, &S) -> <S as State>::Derivative> {
+    derivative: F,
+    absolute_tolerance: Float,
+    relative_tolerance: Float,
+    max_step: Option<Float>,
+    max_steps: usize,
+    _state: PhantomData<S>,
+}
+
+/// The stages of a single Dormand-Prince step
+struct Stages<D> {
+    k: [D; 7],
+    slope: D,
+}
+
+static SOLVE_EXPECTED_MESSAGE: &str =
+    "expected adaptive solve to converge. use DormandPrince::solve instead to handle possible failure";
+
+impl<S, F> DormandPrince<S, F>
+where
+    S: State + Norm,
+    S::Derivative: Norm,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    /// Constructs a new [DormandPrince] solver from a derivative function `f(t, y) -> dy/dt`
+    ///
+    /// Both the absolute and relative tolerances default to `1e-6`
+    pub fn new(derivative: F) -> Self {
+        Self {
+            derivative,
+            absolute_tolerance: Float::from(1e-6),
+            relative_tolerance: Float::from(1e-6),
+            max_step: None,
+            max_steps: 100_000,
+            _state: PhantomData,
+        }
+    }
+
+    /// Sets the absolute and relative error tolerances
+    ///
+    /// A step is accepted when its estimated error is at most
+    /// `absolute + relative * |y|`
+    pub fn with_tolerances<T: Into<Float>>(mut self, absolute: T, relative: T) -> Self {
+        self.absolute_tolerance = absolute.into();
+        self.relative_tolerance = relative.into();
+        self
+    }
+
+    /// Limits the size of any single step
+    pub fn with_max_step<T: Into<Float>>(mut self, max_step: T) -> Self {
+        self.max_step = Some(max_step.into().abs());
+        self
+    }
+
+    /// Limits the number of attempted steps a solve may take before giving up
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Attempt a single step of `dt` from `state` at time `t`
+    ///
+    /// The step is not retried if it is rejected, check [Attempt::accepted] and use
+    /// [Attempt::next_dt] to drive the step size manually
+    pub fn try_step(&self, state: S, t: Float, dt: Float) -> Attempt<S> {
+        let k1 = (self.derivative)(t, &state);
+        self.attempt(state, t, dt, k1).0
+    }
+
+    /// Advance `state` from time `from` to time `to`
+    ///
+    /// Returns [None] if the step size becomes too small to make progress or
+    /// the step limit is exceeded
+    pub fn solve(&self, state: S, from: Float, to: Float) -> Option<Solution<S>> {
+        self.run(state, from, to, None)
+    }
+
+    /// Advance `state` from time `from` to time `to`, also returning a [DenseOutput] that
+    /// can be sampled anywhere in between
+    pub fn solve_dense(
+        &self,
+        state: S,
+        from: Float,
+        to: Float,
+    ) -> Option<(Solution<S>, DenseOutput<S>)> {
+        let mut segments = Vec::new();
+        let solution = self.run(state, from, to, Some(&mut segments))?;
+        Some((solution, DenseOutput { segments }))
+    }
+
+    fn run(
+        &self,
+        state: S,
+        from: Float,
+        to: Float,
+        mut dense: Option<&mut Vec<Segment<S>>>,
+    ) -> Option<Solution<S>> {
+        let mut stats = Statistics::default();
+        let span = to - from;
+        if span == Float::ZERO {
+            return Some(Solution { state, stats });
+        }
+
+        let direction = span.signum();
+        let mut t = from;
+        let mut y = state;
+        let mut k1 = (self.derivative)(t, &y);
+        stats.evaluations += 1;
+
+        let mut dt = self.initial_step(&y, &k1, span);
+        while (to - t) * direction > Float::ZERO {
+            if stats.steps + stats.rejected >= self.max_steps {
+                return None;
+            }
+
+            if let Some(max_step) = self.max_step {
+                dt = direction * Float::min(dt.abs(), max_step);
+            }
+
+            let last = (t + dt - to) * direction >= Float::ZERO;
+            if last {
+                dt = to - t;
+            }
+
+            let (attempt, stages) = self.attempt(y, t, dt, k1);
+            stats.evaluations += 6;
+
+            if attempt.accepted() {
+                if let Some(segments) = dense.as_mut() {
+                    segments.push(Self::segment(y, t, dt, &stages));
+                }
+
+                t = if last { to } else { t + dt };
+                y = attempt.state;
+                k1 = stages.k[6];
+                stats.steps += 1;
+            } else {
+                stats.rejected += 1;
+            }
+
+            dt = attempt.next_dt;
+            if dt.abs() <= Float::EPSILON * Float::max(t.abs(), Float::ONE) {
+                return None;
+            }
+        }
+
+        Some(Solution { state: y, stats })
+    }
+
+    /// Chooses a first step size from the magnitudes of the state and its derivative
+    fn initial_step(&self, state: &S, derivative: &S::Derivative, span: Float) -> Float {
+        let threshold = Float::from(1e-5);
+        let scale = self.absolute_tolerance + self.relative_tolerance * state.norm();
+        let d0 = state.norm() / scale;
+        let d1 = derivative.norm() / scale;
+
+        let dt = if d0 < threshold || d1 < threshold {
+            Float::from(1e-6)
+        } else {
+            Float::from(0.01) * d0 / d1
+        };
+
+        span.signum() * Float::min(dt, span.abs())
+    }
+
+    fn attempt(
+        &self,
+        y: S,
+        t: Float,
+        dt: Float,
+        k1: S::Derivative,
+    ) -> (Attempt<S>, Stages<S::Derivative>) {
+        let f = &self.derivative;
+        let c = |value: f64| Float::from(value);
+
+        let k2 = f(t + dt * c(1.0 / 5.0), &y.advance(&(k1 * c(1.0 / 5.0)), dt));
+        let k3 = f(
+            t + dt * c(3.0 / 10.0),
+            &y.advance(&(k1 * c(3.0 / 40.0) + k2 * c(9.0 / 40.0)), dt),
+        );
+        let k4 = f(
+            t + dt * c(4.0 / 5.0),
+            &y.advance(
+                &(k1 * c(44.0 / 45.0) + k2 * c(-56.0 / 15.0) + k3 * c(32.0 / 9.0)),
+                dt,
+            ),
+        );
+        let k5 = f(
+            t + dt * c(8.0 / 9.0),
+            &y.advance(
+                &(k1 * c(19372.0 / 6561.0)
+                    + k2 * c(-25360.0 / 2187.0)
+                    + k3 * c(64448.0 / 6561.0)
+                    + k4 * c(-212.0 / 729.0)),
+                dt,
+            ),
+        );
+        let k6 = f(
+            t + dt,
+            &y.advance(
+                &(k1 * c(9017.0 / 3168.0)
+                    + k2 * c(-355.0 / 33.0)
+                    + k3 * c(46732.0 / 5247.0)
+                    + k4 * c(49.0 / 176.0)
+                    + k5 * c(-5103.0 / 18656.0)),
+                dt,
+            ),
+        );
+
+        let slope = k1 * c(35.0 / 384.0)
+            + k3 * c(500.0 / 1113.0)
+            + k4 * c(125.0 / 192.0)
+            + k5 * c(-2187.0 / 6784.0)
+            + k6 * c(11.0 / 84.0);
+        let next = y.advance(&slope, dt);
+
+        // The last stage is evaluated at the new state and is reused as the first stage of the next step
+        let k7 = f(t + dt, &next);
+
+        // Difference between the fifth and fourth order solutions
+        let difference = k1 * c(71.0 / 57600.0)
+            + k3 * c(-71.0 / 16695.0)
+            + k4 * c(71.0 / 1920.0)
+            + k5 * c(-17253.0 / 339200.0)
+            + k6 * c(22.0 / 525.0)
+            + k7 * c(-1.0 / 40.0);
+
+        let scale =
+            self.absolute_tolerance + self.relative_tolerance * Float::max(y.norm(), next.norm());
+        let error = (difference * dt).norm() / scale;
+
+        let attempt = Attempt {
+            state: next,
+            error,
+            next_dt: dt * Self::step_factor(error),
+        };
+
+        let stages = Stages {
+            k: [k1, k2, k3, k4, k5, k6, k7],
+            slope,
+        };
+
+        (attempt, stages)
+    }
+
+    /// The factor by which to scale the step size given a normalized error
+    fn step_factor(error: Float) -> Float {
+        let safety = Float::from(0.9);
+        let min = Float::from(0.2);
+        let max = Float::from(10.0);
+
+        if error == Float::ZERO {
+            return max;
+        }
+
+        let factor = safety * Float::powf(error, Float::from(-0.2));
+        if error > Float::ONE {
+            Float::clamp(factor, min, Float::ONE)
+        } else {
+            Float::clamp(factor, min, max)
+        }
+    }
+
+    /// Builds the continuous extension of an accepted step
+    fn segment(start: S, t: Float, dt: Float, stages: &Stages<S::Derivative>) -> Segment<S> {
+        let c = |value: f64| Float::from(value);
+        let negative = -Float::ONE;
+        let [k1, _, k3, k4, k5, k6, k7] = stages.k;
+
+        let difference = stages.slope * dt;
+        let spline = k1 * dt + difference * negative;
+        let curvature = difference + k7 * (-dt) + spline * negative;
+        let correction = (k1 * c(-12715105075.0 / 11282082432.0)
+            + k3 * c(87487479700.0 / 32700410799.0)
+            + k4 * c(-10690763975.0 / 1880347072.0)
+            + k5 * c(701980252875.0 / 199316789632.0)
+            + k6 * c(-1453857185.0 / 822651844.0)
+            + k7 * c(69997945.0 / 29380423.0))
+            * dt;
+
+        Segment {
+            t,
+            dt,
+            start,
+            coefficients: [difference, spline, curvature, correction],
+        }
+    }
+}
+
+impl<S, F> Integrator for DormandPrince<S, F>
+where
+    S: State<Derivative = S> + Norm,
+{
+    type Input = (Float, S);
+    type Output = S;
+
+    fn integrate<G>(from: Self::Input, to: Self::Input, func: G) -> Self::Output
+    where
+        G: Fn(Self::Input) -> Self::Output,
+    {
+        DormandPrince::new(|t, y: &S| func((t, *y)))
+            .solve(from.1, from.0, to.0)
+            .expect(SOLVE_EXPECTED_MESSAGE)
+            .state
+    }
+}
+
+#[cfg(test)]
+mod adaptive_tests {
+    use super::*;
+    use crate::constant::E;
+    use crate::constant::PI;
+    use crate::integrate::Phase;
+    use crate::traits::Approximately;
+    use crate::Point;
+    use crate::Vector;
+
+    fn decay(_t: Float, y: &Float) -> Float {
+        -*y
+    }
+
+    fn oscillator(_t: Float, s: &Phase<Float>) -> Phase<Float> {
+        Phase {
+            position: s.velocity,
+            velocity: -s.position,
+        }
+    }
+
+    #[test]
+    fn exponential_decay() {
+        let solver = DormandPrince::new(decay).with_tolerances(1e-10, 1e-10);
+        let solution = solver
+            .solve(Float::from(1.0), Float::from(0.0), Float::from(2.0))
+            .unwrap();
+        assert!(solution
+            .state
+            .approximately(Float::from(1.0) / (E * E), Float::from(1e-9)));
+    }
+
+    #[test]
+    fn statistics() {
+        let solver = DormandPrince::new(decay);
+        let solution = solver
+            .solve(Float::from(1.0), Float::from(0.0), Float::from(10.0))
+            .unwrap();
+        let stats = solution.stats;
+        assert!(stats.steps > 0);
+        assert_eq!(
+            stats.evaluations,
+            1 + 6 * (stats.steps + stats.rejected),
+            "the last stage of each step should be reused"
+        );
+    }
+
+    #[test]
+    fn tighter_tolerance_takes_more_steps() {
+        let loose = DormandPrince::new(decay).with_tolerances(1e-3, 1e-3);
+        let tight = DormandPrince::new(decay).with_tolerances(1e-10, 1e-10);
+        let loose = loose
+            .solve(Float::from(1.0), Float::from(0.0), Float::from(5.0))
+            .unwrap();
+        let tight = tight
+            .solve(Float::from(1.0), Float::from(0.0), Float::from(5.0))
+            .unwrap();
+        assert!(tight.stats.steps > loose.stats.steps);
+    }
+
+    #[test]
+    fn rejects_large_steps() {
+        let solver = DormandPrince::new(decay).with_tolerances(1e-8, 1e-8);
+        let attempt = solver.try_step(Float::from(1.0), Float::from(0.0), Float::from(5.0));
+        assert!(!attempt.accepted());
+        assert!(attempt.next_dt < Float::from(5.0));
+
+        let attempt = solver.try_step(Float::from(1.0), Float::from(0.0), Float::from(0.001));
+        assert!(attempt.accepted());
+        assert!(attempt.next_dt > Float::from(0.001));
+    }
+
+    #[test]
+    fn rejected_steps_are_counted() {
+        // A mildly stiff problem keeps pushing the step size against the stability limit
+        let stiff = |t: Float, y: &Float| Float::from(-50.0) * (*y - t.cos());
+        let solver = DormandPrince::new(stiff).with_tolerances(1e-4, 1e-4);
+        let solution = solver
+            .solve(Float::from(0.0), Float::from(0.0), Float::from(10.0))
+            .unwrap();
+        assert!(solution.stats.rejected > 0);
+        assert_eq!(
+            solution.stats.evaluations,
+            1 + 6 * (solution.stats.steps + solution.stats.rejected)
+        );
+    }
+
+    #[test]
+    fn backwards_in_time() {
+        let solver = DormandPrince::new(decay).with_tolerances(1e-10, 1e-10);
+        let solution = solver
+            .solve(Float::from(1.0) / E, Float::from(1.0), Float::from(0.0))
+            .unwrap();
+        assert!(solution
+            .state
+            .approximately(Float::from(1.0), Float::from(1e-9)));
+    }
+
+    #[test]
+    fn dense_output() {
+        let solver = DormandPrince::new(oscillator).with_tolerances(1e-8, 1e-8);
+        let start = Phase::new(Float::from(0.0), Float::from(1.0));
+        let (solution, dense) = solver
+            .solve_dense(start, Float::from(0.0), Float::from(2.0) * PI)
+            .unwrap();
+
+        assert_eq!(
+            dense.span(),
+            Some((Float::from(0.0), Float::from(2.0) * PI))
+        );
+        assert!(solution.stats.steps < 100);
+
+        for i in 0..=100 {
+            let t = Float::from(i as f64 * 0.0628);
+            let sample = dense.sample(t).unwrap();
+            assert!(sample.position.approximately(t.sin(), Float::from(1e-6)));
+            assert!(sample.velocity.approximately(t.cos(), Float::from(1e-6)));
+        }
+
+        assert!(dense.sample(Float::from(-0.1)).is_none());
+        assert!(dense.sample(Float::from(7.0)).is_none());
+    }
+
+    #[test]
+    fn circular_orbit() {
+        // Unit circular orbit around the origin with GM = 1, one revolution takes 2π
+        let gravity = |_t: Float, s: &Phase<Point>| {
+            let r = s.position.as_vector();
+            Phase {
+                position: s.velocity,
+                velocity: r * (-Float::ONE / r.length_sq().powf(Float::from(1.5))),
+            }
+        };
+        let start = Phase::new(Point::new(1.0, 0.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        let solver = DormandPrince::new(gravity).with_tolerances(1e-10, 1e-10);
+        let solution = solver
+            .solve(start, Float::from(0.0), Float::from(2.0) * PI)
+            .unwrap();
+        assert!(solution
+            .state
+            .position
+            .approximately(&start.position, Float::from(1e-7)));
+    }
+
+    #[test]
+    fn step_limit() {
+        let solver = DormandPrince::new(decay)
+            .with_max_step(0.01)
+            .with_max_steps(10);
+        assert!(solver
+            .solve(Float::from(1.0), Float::from(0.0), Float::from(1.0))
+            .is_none());
+    }
+
+    #[test]
+    fn integrator_trait() {
+        let y = DormandPrince::<Float>::integrate(
+            (Float::from(0.0), Float::from(1.0)),
+            (Float::from(1.0), Float::from(0.0)),
+            |(_t, y)| -y,
+        );
+        assert!(y.approximately(Float::from(1.0) / E, Float::from(1e-5)));
+    }
+}
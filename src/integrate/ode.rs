@@ -0,0 +1,315 @@
+//!
+//! Fixed-step ordinary differential equation solvers
+//!
+
+use std::marker::PhantomData;
+
+use crate::integrate::Integrator;
+use crate::integrate::Phase;
+use crate::integrate::State;
+use crate::integrate::Stepper;
+use crate::integrate::DEFAULT_STEPS;
+use crate::Float;
+
+/// The explicit (forward) Euler method
+///
+/// First order accurate, the simplest and cheapest method available. Only suitable for
+/// small time steps and well behaved derivatives
+///
+/// ```
+/// # use integrator::{ Float, integrate::{ Euler, Stepper } };
+/// let decay = Euler::new(|_t: Float, y: &Float| -*y);
+/// let y = decay.step(1.0, 0.0, 0.1);
+/// assert_eq!(y, 0.9);
+/// ```
+pub struct Euler<S: State, F = fn(Float, &S) -> <S as State>::Derivative> {
+    derivative: F,
+    _state: PhantomData<S>,
+}
+
+impl<S, F> Euler<S, F>
+where
+    S: State,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    /// Constructs a new [Euler] stepper from a derivative function `f(t, y) -> dy/dt`
+    pub fn new(derivative: F) -> Self {
+        Self {
+            derivative,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<S, F> Stepper<S> for Euler<S, F>
+where
+    S: State,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    #[inline]
+    fn step(&self, state: S, t: Float, dt: Float) -> S {
+        state.advance(&(self.derivative)(t, &state), dt)
+    }
+}
+
+impl<S: State<Derivative = S>, F> Integrator for Euler<S, F> {
+    type Input = (Float, S);
+    type Output = S;
+
+    fn integrate<G>(from: Self::Input, to: Self::Input, func: G) -> Self::Output
+    where
+        G: Fn(Self::Input) -> Self::Output,
+    {
+        Euler::new(|t, y: &S| func((t, *y))).solve(from.1, from.0, to.0, DEFAULT_STEPS)
+    }
+}
+
+/// The semi-implicit (symplectic) Euler method
+///
+/// Operates on a [Phase] using an acceleration function `f(t, x, v) -> a`. The velocity is
+/// advanced first and the new velocity is used to advance the position. This is still first
+/// order accurate but, unlike [Euler], keeps the energy of oscillating systems bounded
+pub struct SemiImplicitEuler<
+    S: State,
+    F = fn(Float, &S, &<S as State>::Derivative) -> <S as State>::Derivative,
+> {
+    acceleration: F,
+    _state: PhantomData<S>,
+}
+
+impl<S, F> SemiImplicitEuler<S, F>
+where
+    S: State,
+    F: Fn(Float, &S, &S::Derivative) -> S::Derivative,
+{
+    /// Constructs a new [SemiImplicitEuler] stepper from an acceleration function `f(t, x, v) -> a`
+    pub fn new(acceleration: F) -> Self {
+        Self {
+            acceleration,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<S, F> Stepper<Phase<S>> for SemiImplicitEuler<S, F>
+where
+    S: State,
+    S::Derivative: State<Derivative = S::Derivative>,
+    F: Fn(Float, &S, &S::Derivative) -> S::Derivative,
+{
+    #[inline]
+    fn step(&self, state: Phase<S>, t: Float, dt: Float) -> Phase<S> {
+        let acceleration = (self.acceleration)(t, &state.position, &state.velocity);
+        let velocity = state.velocity.advance(&acceleration, dt);
+        let position = state.position.advance(&velocity, dt);
+        Phase { position, velocity }
+    }
+}
+
+impl<S: State<Derivative = S>, F> Integrator for SemiImplicitEuler<S, F> {
+    type Input = (Float, Phase<S>);
+    type Output = Phase<S>;
+
+    /// The velocity of the derivative returned by `func` is used as the acceleration
+    fn integrate<G>(from: Self::Input, to: Self::Input, func: G) -> Self::Output
+    where
+        G: Fn(Self::Input) -> Self::Output,
+    {
+        SemiImplicitEuler::new(|t, x: &S, v: &S| func((t, Phase::new(*x, *v))).velocity).solve(
+            from.1,
+            from.0,
+            to.0,
+            DEFAULT_STEPS,
+        )
+    }
+}
+
+/// The classic fourth order Runge-Kutta method
+///
+/// Evaluates the derivative four times per step, giving a global error proportional to `dt^4`
+pub struct RungeKutta4<S: State, F = fn(Float, &S) -> <S as State>::Derivative> {
+    derivative: F,
+    _state: PhantomData<S>,
+}
+
+impl<S, F> RungeKutta4<S, F>
+where
+    S: State,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    /// Constructs a new [RungeKutta4] stepper from a derivative function `f(t, y) -> dy/dt`
+    pub fn new(derivative: F) -> Self {
+        Self {
+            derivative,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<S, F> Stepper<S> for RungeKutta4<S, F>
+where
+    S: State,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    fn step(&self, state: S, t: Float, dt: Float) -> S {
+        let f = &self.derivative;
+        let two = Float::from(2.0);
+        let half = dt / two;
+
+        let k1 = f(t, &state);
+        let k2 = f(t + half, &state.advance(&k1, half));
+        let k3 = f(t + half, &state.advance(&k2, half));
+        let k4 = f(t + dt, &state.advance(&k3, dt));
+
+        let slope = k1 + k2 * two + k3 * two + k4;
+        state.advance(&slope, dt / Float::from(6.0))
+    }
+}
+
+impl<S: State<Derivative = S>, F> Integrator for RungeKutta4<S, F> {
+    type Input = (Float, S);
+    type Output = S;
+
+    fn integrate<G>(from: Self::Input, to: Self::Input, func: G) -> Self::Output
+    where
+        G: Fn(Self::Input) -> Self::Output,
+    {
+        RungeKutta4::new(|t, y: &S| func((t, *y))).solve(from.1, from.0, to.0, DEFAULT_STEPS)
+    }
+}
+
+#[cfg(test)]
+mod ode_tests {
+    use super::*;
+    use crate::constant::E;
+    use crate::constant::PI;
+    use crate::traits::Approximately;
+    use crate::Point;
+    use crate::Vector;
+
+    fn decay(_t: Float, y: &Float) -> Float {
+        -*y
+    }
+
+    fn spring(_t: Float, x: &Vector, _v: &Vector) -> Vector {
+        -*x
+    }
+
+    #[test]
+    fn euler_single_step() {
+        let euler = Euler::new(decay);
+        let y = euler.step(Float::from(2.0), Float::from(0.0), Float::from(0.5));
+        assert!(y.approximately(Float::from(1.0), Float::from(1e-9)));
+    }
+
+    #[test]
+    fn euler_exponential_decay() {
+        let euler = Euler::new(decay);
+        let y = euler.solve(Float::from(1.0), Float::from(0.0), Float::from(1.0), 10_000);
+        assert!(y.approximately(Float::from(1.0) / E, Float::from(1e-4)));
+    }
+
+    #[test]
+    fn runge_kutta_exponential_decay() {
+        let rk4 = RungeKutta4::new(decay);
+        let y = rk4.solve(Float::from(1.0), Float::from(0.0), Float::from(1.0), 100);
+        assert!(y.approximately(Float::from(1.0) / E, Float::from(1e-9)));
+    }
+
+    #[test]
+    fn runge_kutta_is_fourth_order() {
+        let rk4 = RungeKutta4::new(decay);
+        let exact = Float::from(1.0) / E;
+        let coarse = rk4.solve(Float::from(1.0), Float::from(0.0), Float::from(1.0), 10) - exact;
+        let fine = rk4.solve(Float::from(1.0), Float::from(0.0), Float::from(1.0), 20) - exact;
+        let ratio = coarse.abs() / fine.abs();
+        assert!(ratio > Float::from(14.0) && ratio < Float::from(18.0));
+    }
+
+    #[test]
+    fn runge_kutta_time_dependent() {
+        // y' = cos(t), y(0) = 0 => y(t) = sin(t)
+        let rk4 = RungeKutta4::new(|t: Float, _y: &Float| t.cos());
+        let y = rk4.solve(
+            Float::from(0.0),
+            Float::from(0.0),
+            PI / Float::from(2.0),
+            50,
+        );
+        assert!(y.approximately(Float::from(1.0), Float::from(1e-8)));
+    }
+
+    #[test]
+    fn vector_state() {
+        let rk4 =
+            RungeKutta4::new(|_t: Float, v: &Vector| Vector::new(-v.x, v.y, Float::from(0.0)));
+        let v = rk4.solve(
+            Vector::new(1.0, 1.0, 1.0),
+            Float::from(0.0),
+            Float::from(1.0),
+            100,
+        );
+        assert!(v.approximately(Vector::new(1.0 / E, E, 1.0), Float::from(1e-8)));
+    }
+
+    #[test]
+    fn point_state() {
+        let velocity = Vector::new(1.0, -2.0, 0.5);
+        let euler = Euler::new(|_t: Float, _p: &Point| velocity);
+        let p = euler.solve(Point::origin(), Float::from(0.0), Float::from(2.0), 8);
+        assert!(p.approximately(&Point::new(2.0, -4.0, 1.0), Float::from(1e-9)));
+    }
+
+    #[test]
+    fn phase_state() {
+        // Constant acceleration: x = x0 + v0 t + a t^2 / 2
+        let rk4 = RungeKutta4::new(|_t: Float, s: &Phase<Point>| Phase {
+            position: s.velocity,
+            velocity: Vector::new(0.0, -10.0, 0.0),
+        });
+        let start = Phase::new(Point::origin(), Vector::new(1.0, 0.0, 0.0));
+        let end = rk4.solve(start, Float::from(0.0), Float::from(1.0), 10);
+        assert!(end
+            .position
+            .approximately(&Point::new(1.0, -5.0, 0.0), Float::from(1e-9)));
+        assert!(end
+            .velocity
+            .approximately(Vector::new(1.0, -10.0, 0.0), Float::from(1e-9)));
+    }
+
+    #[test]
+    fn semi_implicit_euler_bounded_energy() {
+        let sie = SemiImplicitEuler::new(spring);
+        let mut state = Phase::new(Vector::unit_x(), Vector::default());
+        let dt = Float::from(0.01);
+        for i in 0..10_000 {
+            state = sie.step(state, dt * Float::from(i as f64), dt);
+            let energy = (state.position.length_sq() + state.velocity.length_sq()) / 2.0;
+            assert!(energy.approximately(Float::from(0.5), Float::from(0.01)));
+        }
+    }
+
+    #[test]
+    fn integrator_trait() {
+        let from = (Float::from(0.0), Float::from(1.0));
+        let to = (Float::from(1.0), Float::from(0.0));
+
+        let euler = Euler::<Float>::integrate(from, to, |(_t, y)| -y);
+        let rk4 = RungeKutta4::<Float>::integrate(from, to, |(_t, y)| -y);
+        assert!(euler.approximately(Float::from(1.0) / E, Float::from(1e-3)));
+        assert!(rk4.approximately(Float::from(1.0) / E, Float::from(1e-9)));
+
+        let from = (
+            Float::from(0.0),
+            Phase::new(Float::from(1.0), Float::from(0.0)),
+        );
+        let to = (Float::from(2.0) * PI, Phase::default());
+        let sie = SemiImplicitEuler::<Float>::integrate(from, to, |(_t, s)| Phase {
+            position: s.velocity,
+            velocity: -s.position,
+        });
+        assert!(sie
+            .position
+            .approximately(Float::from(1.0), Float::from(1e-2)));
+    }
+}
@@ -0,0 +1,195 @@
+//!
+//! Lie group integrators for orientations
+//!
+//! Orientations live on the manifold of unit [Rotor]s rather than in a vector space, so
+//! adding a scaled derivative to them drifts off the manifold. The methods here instead
+//! advance a [Rotor] with the exponential map of its angular velocity, keeping it a pure
+//! rotation at every step without any ad-hoc renormalization
+//!
+//! Angular velocities are given as a function `f(t, r) -> ω` returning a world space
+//! [Bivector], matching [Rotor::advance]. For a constant angular velocity a single call
+//! to [Rotor::advance] is already exact
+//!
+
+use crate::bivec::Bivector;
+use crate::integrate::Stepper;
+use crate::rotor::Rotor;
+use crate::traits::FloatExt;
+use crate::Float;
+use crate::Vector;
+
+/// The exponential map of a rotation vector
+#[inline]
+fn exp(rotation: Vector) -> Rotor {
+    Rotor::from_angular_velocity(Bivector::from_axis_vector(rotation), Float::ONE)
+}
+
+/// The fourth order Runge-Kutta-Munthe-Kaas method
+///
+/// Performs the classic fourth order Runge-Kutta method in the Lie algebra of rotation vectors,
+/// correcting each stage with a truncated inverse of the derivative of the exponential map
+pub struct MuntheKaas4<F> {
+    angular_velocity: F,
+}
+
+impl<F> MuntheKaas4<F>
+where
+    F: Fn(Float, &Rotor) -> Bivector,
+{
+    /// Constructs a new [MuntheKaas4] stepper from an angular velocity function `f(t, r) -> ω`
+    pub fn new(angular_velocity: F) -> Self {
+        Self { angular_velocity }
+    }
+
+    /// Evaluates the angular velocity at the orientation `exp(u) r`, pulled back into the
+    /// algebra at `u` with `dexp⁻¹(u, w) ≈ w - [u, w] / 2 + [u, [u, w]] / 12`
+    fn stage(&self, orientation: &Rotor, u: Vector, t: Float, dt: Float) -> Vector {
+        let w = (self.angular_velocity)(t, &(exp(u) * orientation)).to_axis_vector() * dt;
+        let uw = u.cross(&w);
+        let uuw = u.cross(&uw);
+        w - uw / Float::from(2.0) + uuw / Float::from(12.0)
+    }
+}
+
+impl<F> Stepper<Rotor> for MuntheKaas4<F>
+where
+    F: Fn(Float, &Rotor) -> Bivector,
+{
+    fn step(&self, state: Rotor, t: Float, dt: Float) -> Rotor {
+        let two = Float::from(2.0);
+        let half = dt / two;
+
+        let k1 = (self.angular_velocity)(t, &state).to_axis_vector() * dt;
+        let k2 = self.stage(&state, k1 / two, t + half, dt);
+        let k3 = self.stage(&state, k2 / two, t + half, dt);
+        let k4 = self.stage(&state, k3, t + dt, dt);
+
+        let u = (k1 + k2 * two + k3 * two + k4) / Float::from(6.0);
+        exp(u) * state
+    }
+}
+
+/// The third order Crouch-Grossman method
+///
+/// Composes the exponentials of the angular velocity at each stage directly, never leaving
+/// the group. Cheaper per step than [MuntheKaas4] at the cost of one order of accuracy
+pub struct CrouchGrossman3<F> {
+    angular_velocity: F,
+}
+
+impl<F> CrouchGrossman3<F>
+where
+    F: Fn(Float, &Rotor) -> Bivector,
+{
+    /// Constructs a new [CrouchGrossman3] stepper from an angular velocity function `f(t, r) -> ω`
+    pub fn new(angular_velocity: F) -> Self {
+        Self { angular_velocity }
+    }
+}
+
+impl<F> Stepper<Rotor> for CrouchGrossman3<F>
+where
+    F: Fn(Float, &Rotor) -> Bivector,
+{
+    fn step(&self, state: Rotor, t: Float, dt: Float) -> Rotor {
+        let c = |value: f64| Float::from(value);
+        let f = |t: Float, r: &Rotor| (self.angular_velocity)(t, r).to_axis_vector() * dt;
+
+        let k1 = f(t, &state);
+        let y2 = exp(k1 * c(3.0 / 4.0)) * state;
+        let k2 = f(t + dt * c(3.0 / 4.0), &y2);
+        let y3 = exp(k2 * c(17.0 / 108.0)) * exp(k1 * c(119.0 / 216.0)) * state;
+        let k3 = f(t + dt * c(17.0 / 24.0), &y3);
+
+        exp(k3 * c(24.0 / 17.0)) * exp(k2 * c(-2.0 / 3.0)) * exp(k1 * c(13.0 / 51.0)) * state
+    }
+}
+
+#[cfg(test)]
+mod orientation_tests {
+    use super::*;
+    use crate::constant::PI;
+    use crate::traits::Approximately;
+
+    /// An angular velocity whose axis precesses about z, so successive rotations do not commute
+    fn precessing(t: Float, _r: &Rotor) -> Bivector {
+        Bivector::from_axis_vector(Vector::new(t.cos(), t.sin(), Float::from(0.5)))
+    }
+
+    /// How far apart two orientations are, measured by where they send a few test vectors
+    fn difference(a: &Rotor, b: &Rotor) -> Float {
+        [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()]
+            .iter()
+            .map(|v| (v.rotated_by(a) - v.rotated_by(b)).length())
+            .fold(Float::ZERO, Float::max)
+    }
+
+    fn reference() -> Rotor {
+        MuntheKaas4::new(precessing).solve(Rotor::identity(), Float::ZERO, Float::from(2.0), 4000)
+    }
+
+    #[test]
+    fn constant_angular_velocity_is_exact() {
+        let velocity = Bivector::from_axis_vector(Vector::new(0.0, 0.0, PI));
+        let constant = |_t: Float, _r: &Rotor| velocity;
+
+        let rkmk = MuntheKaas4::new(constant).solve(Rotor::identity(), Float::ZERO, Float::ONE, 3);
+        let cg =
+            CrouchGrossman3::new(constant).solve(Rotor::identity(), Float::ZERO, Float::ONE, 3);
+
+        let expected = Vector::new(-1.0, 0.0, 0.0);
+        assert!(Vector::unit_x()
+            .rotated_by(&rkmk)
+            .approximately(expected, Float::from(1e-12)));
+        assert!(Vector::unit_x()
+            .rotated_by(&cg)
+            .approximately(expected, Float::from(1e-12)));
+    }
+
+    #[test]
+    fn munthe_kaas_is_fourth_order() {
+        let exact = reference();
+        let rkmk = MuntheKaas4::new(precessing);
+        let coarse = rkmk.solve(Rotor::identity(), Float::ZERO, Float::from(2.0), 20);
+        let fine = rkmk.solve(Rotor::identity(), Float::ZERO, Float::from(2.0), 40);
+        let ratio = difference(&coarse, &exact) / difference(&fine, &exact);
+        assert!(ratio > Float::from(13.0) && ratio < Float::from(19.0));
+    }
+
+    #[test]
+    fn crouch_grossman_is_third_order() {
+        let exact = reference();
+        let cg = CrouchGrossman3::new(precessing);
+        let coarse = cg.solve(Rotor::identity(), Float::ZERO, Float::from(2.0), 40);
+        let fine = cg.solve(Rotor::identity(), Float::ZERO, Float::from(2.0), 80);
+        let ratio = difference(&coarse, &exact) / difference(&fine, &exact);
+        assert!(ratio > Float::from(6.5) && ratio < Float::from(9.5));
+    }
+
+    #[test]
+    fn higher_order_beats_first_order() {
+        let exact = reference();
+        let mut euler = Rotor::identity();
+        let dt = Float::from(0.05);
+        for i in 0..40 {
+            let t = dt * Float::from(i as f64);
+            euler.advance(precessing(t, &euler), dt);
+        }
+        let rkmk = MuntheKaas4::new(precessing).solve(
+            Rotor::identity(),
+            Float::ZERO,
+            Float::from(2.0),
+            40,
+        );
+        assert!(difference(&rkmk, &exact) * Float::from(1000.0) < difference(&euler, &exact));
+    }
+
+    #[test]
+    fn stays_on_manifold() {
+        let rkmk = MuntheKaas4::new(precessing);
+        let orientation = rkmk.solve(Rotor::identity(), Float::ZERO, Float::from(100.0), 10_000);
+        assert!(orientation
+            .magnitude()
+            .approximately(Float::ONE, Float::from(1e-12)));
+    }
+}
@@ -0,0 +1,541 @@
+//!
+//! Numerical quadrature of definite integrals
+//!
+//! Each method returns an [Estimate] holding both the value of the integral and an estimate
+//! of its absolute error. Integrals over unbounded intervals are supported through
+//! [Quadrature::estimate_improper], which maps the interval onto a finite one
+//!
+
+use crate::integrate::Integrator;
+use crate::traits::FloatExt;
+use crate::traits::FromLossy;
+use crate::Float;
+
+/// The value of an integral along with an estimate of its absolute error
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub value: Float,
+    pub error: Float,
+}
+
+/// A method for computing definite integrals of `Float -> Float` functions
+pub trait Quadrature {
+    /// Estimate the integral of `func` from `from` to `to`
+    fn estimate<F>(&self, from: Float, to: Float, func: F) -> Estimate
+    where
+        F: Fn(Float) -> Float;
+
+    /// Estimate an integral where either bound may be infinite
+    ///
+    /// A bound of [None] is treated as infinite, negative for `from` and positive for `to`.
+    /// Unbounded intervals are mapped onto finite ones by a change of variables, which assumes
+    /// that `func` decays to zero faster than `1/x^2`
+    fn estimate_improper<F>(&self, from: Option<Float>, to: Option<Float>, func: F) -> Estimate
+    where
+        F: Fn(Float) -> Float,
+    {
+        let zer = Float::ZERO;
+        let one = Float::ONE;
+
+        match (from, to) {
+            (Some(from), Some(to)) => self.estimate(from, to, func),
+            // x = a + t / (1 - t)
+            (Some(from), None) => self.estimate(zer, one, |t| {
+                let s = one - t;
+                if s == zer {
+                    zer
+                } else {
+                    func(from + t / s) / (s * s)
+                }
+            }),
+            // x = b - t / (1 - t)
+            (None, Some(to)) => self.estimate(zer, one, |t| {
+                let s = one - t;
+                if s == zer {
+                    zer
+                } else {
+                    func(to - t / s) / (s * s)
+                }
+            }),
+            // x = t / (1 - t^2)
+            (None, None) => self.estimate(-one, one, |t| {
+                let s = one - t * t;
+                if s == zer {
+                    zer
+                } else {
+                    func(t / s) * (one + t * t) / (s * s)
+                }
+            }),
+        }
+    }
+}
+
+/// Adaptive Simpson's rule
+///
+/// Recursively bisects the interval until Simpson's rule agrees with the sum of its two
+/// halves, applying a Richardson correction to each accepted interval
+///
+/// ```
+/// # use integrator::{ Float, integrate::quadrature::{ AdaptiveSimpson, Quadrature } };
+/// let simpson = AdaptiveSimpson::default();
+/// let area = simpson.estimate(0.0, 1.0, |x: Float| x * x);
+/// assert!((area.value - 1.0 / 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveSimpson {
+    tolerance: Float,
+    max_depth: usize,
+}
+
+impl AdaptiveSimpson {
+    pub fn new<F: Into<Float>>(tolerance: F, max_depth: usize) -> Self {
+        Self {
+            tolerance: tolerance.into(),
+            max_depth,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn recurse<F>(
+        &self,
+        func: &F,
+        (a, fa): (Float, Float),
+        (m, fm): (Float, Float),
+        (b, fb): (Float, Float),
+        whole: Float,
+        tolerance: Float,
+        depth: usize,
+    ) -> Estimate
+    where
+        F: Fn(Float) -> Float,
+    {
+        let two = Float::from(2.0);
+        let left_m = (a + m) / two;
+        let right_m = (m + b) / two;
+        let f_left_m = func(left_m);
+        let f_right_m = func(right_m);
+
+        let left = simpson((m - a) / two, fa, f_left_m, fm);
+        let right = simpson((b - m) / two, fm, f_right_m, fb);
+        let difference = left + right - whole;
+        let fifteen = Float::from(15.0);
+
+        if depth == 0 || difference.abs() <= fifteen * tolerance {
+            return Estimate {
+                value: left + right + difference / fifteen,
+                error: difference.abs() / fifteen,
+            };
+        }
+
+        let half = tolerance / two;
+        let l = self.recurse(
+            func,
+            (a, fa),
+            (left_m, f_left_m),
+            (m, fm),
+            left,
+            half,
+            depth - 1,
+        );
+        let r = self.recurse(
+            func,
+            (m, fm),
+            (right_m, f_right_m),
+            (b, fb),
+            right,
+            half,
+            depth - 1,
+        );
+
+        Estimate {
+            value: l.value + r.value,
+            error: l.error + r.error,
+        }
+    }
+}
+
+impl Default for AdaptiveSimpson {
+    fn default() -> Self {
+        Self::new(1e-10, 50)
+    }
+}
+
+/// Simpson's rule over an interval of width `2h` given the function values at both ends and the midpoint
+#[inline]
+fn simpson(h: Float, fa: Float, fm: Float, fb: Float) -> Float {
+    h / Float::from(3.0) * (fa + Float::from(4.0) * fm + fb)
+}
+
+impl Quadrature for AdaptiveSimpson {
+    fn estimate<F>(&self, from: Float, to: Float, func: F) -> Estimate
+    where
+        F: Fn(Float) -> Float,
+    {
+        let m = (from + to) / Float::from(2.0);
+        let (fa, fm, fb) = (func(from), func(m), func(to));
+        let whole = simpson(m - from, fa, fm, fb);
+        self.recurse(
+            &func,
+            (from, fa),
+            (m, fm),
+            (to, fb),
+            whole,
+            self.tolerance,
+            self.max_depth,
+        )
+    }
+}
+
+/// Romberg integration
+///
+/// Repeatedly halves the step of the trapezoidal rule and uses Richardson extrapolation on
+/// the sequence of results. Converges very quickly for smooth functions
+#[derive(Debug, Clone, Copy)]
+pub struct Romberg {
+    tolerance: Float,
+    max_iterations: usize,
+}
+
+impl Romberg {
+    pub fn new<F: Into<Float>>(tolerance: F, max_iterations: usize) -> Self {
+        Self {
+            tolerance: tolerance.into(),
+            max_iterations,
+        }
+    }
+}
+
+impl Default for Romberg {
+    fn default() -> Self {
+        Self::new(1e-10, 20)
+    }
+}
+
+impl Quadrature for Romberg {
+    fn estimate<F>(&self, from: Float, to: Float, func: F) -> Estimate
+    where
+        F: Fn(Float) -> Float,
+    {
+        let two = Float::from(2.0);
+        let mut h = to - from;
+        let mut previous = vec![h / two * (func(from) + func(to))];
+        let mut estimate = Estimate {
+            value: previous[0],
+            error: Float::ZERO,
+        };
+
+        for n in 1..=self.max_iterations {
+            h /= two;
+
+            // Only the new midpoints need to be evaluated, the rest are carried over
+            let points = 1i64 << (n - 1);
+            let mut sum = Float::ZERO;
+            for k in 0..points {
+                sum += func(from + h * Float::from_lossy(2 * k + 1));
+            }
+
+            let mut current = Vec::with_capacity(n + 1);
+            current.push(previous[0] / two + h * sum);
+
+            let mut factor = Float::ONE;
+            for m in 1..=n {
+                factor *= Float::from(4.0);
+                let extrapolated =
+                    current[m - 1] + (current[m - 1] - previous[m - 1]) / (factor - Float::ONE);
+                current.push(extrapolated);
+            }
+
+            estimate = Estimate {
+                value: current[n],
+                error: (current[n] - previous[n - 1]).abs(),
+            };
+
+            // Very early agreement can be a coincidence of the sample points, so require a few rows
+            if n >= 4 && estimate.error <= self.tolerance {
+                break;
+            }
+
+            previous = current;
+        }
+
+        estimate
+    }
+}
+
+#[allow(clippy::excessive_precision)]
+const KRONROD_NODES: [f64; 8] = [
+    0.991455371120812639206854697526329,
+    0.949107912342758524526189684047851,
+    0.864864423359769072789712788640926,
+    0.741531185599394439863864773280788,
+    0.586087235467691130294144845693013,
+    0.405845151377397166906606412076961,
+    0.207784955007898467600689403773245,
+    0.000000000000000000000000000000000,
+];
+
+#[allow(clippy::excessive_precision)]
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022935322010529224963732008058970,
+    0.063092092629978553290700663189204,
+    0.104790010322250183839876322541518,
+    0.140653259715525918745189590510238,
+    0.169004726639267902826583426598550,
+    0.190350578064785409913256402421014,
+    0.204432940075298892414161999234649,
+    0.209482141084727828012999174891714,
+];
+
+/// Weights of the 7 point Gauss rule, whose nodes are the odd Kronrod nodes and the center
+#[allow(clippy::excessive_precision)]
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129484966168869693270611432679082,
+    0.279705391489276667901467771423780,
+    0.381830050505118944950369775488975,
+    0.417959183673469387755102040816327,
+];
+
+/// Adaptive Gauss-Kronrod (G7-K15) quadrature
+///
+/// Each interval is integrated with a 15 point Kronrod rule, and the embedded 7 point Gauss
+/// rule gives the error estimate for free. The interval with the largest error is bisected
+/// until the total error is within tolerance. The endpoints are never evaluated, so
+/// integrable singularities at either end are handled gracefully
+#[derive(Debug, Clone, Copy)]
+pub struct GaussKronrod {
+    tolerance: Float,
+    max_intervals: usize,
+}
+
+struct Interval {
+    from: Float,
+    to: Float,
+    estimate: Estimate,
+}
+
+impl GaussKronrod {
+    pub fn new<F: Into<Float>>(tolerance: F, max_intervals: usize) -> Self {
+        Self {
+            tolerance: tolerance.into(),
+            max_intervals,
+        }
+    }
+
+    /// Apply the G7-K15 rule to a single interval
+    fn rule<F>(from: Float, to: Float, func: &F) -> Estimate
+    where
+        F: Fn(Float) -> Float,
+    {
+        let two = Float::from(2.0);
+        let center = (from + to) / two;
+        let half = (to - from) / two;
+
+        let f_center = func(center);
+        let mut kronrod = f_center * Float::from(KRONROD_WEIGHTS[7]);
+        let mut gauss = f_center * Float::from(GAUSS_WEIGHTS[3]);
+
+        for i in 0..7 {
+            let offset = half * Float::from(KRONROD_NODES[i]);
+            let sum = func(center - offset) + func(center + offset);
+            kronrod += sum * Float::from(KRONROD_WEIGHTS[i]);
+            if i % 2 == 1 {
+                gauss += sum * Float::from(GAUSS_WEIGHTS[i / 2]);
+            }
+        }
+
+        Estimate {
+            value: kronrod * half,
+            error: ((kronrod - gauss) * half).abs(),
+        }
+    }
+}
+
+impl Default for GaussKronrod {
+    fn default() -> Self {
+        Self::new(1e-10, 500)
+    }
+}
+
+impl Quadrature for GaussKronrod {
+    fn estimate<F>(&self, from: Float, to: Float, func: F) -> Estimate
+    where
+        F: Fn(Float) -> Float,
+    {
+        let mut intervals = vec![Interval {
+            from,
+            to,
+            estimate: Self::rule(from, to, &func),
+        }];
+
+        let total = |intervals: &[Interval]| {
+            intervals
+                .iter()
+                .fold(Estimate::default(), |sum, interval| Estimate {
+                    value: sum.value + interval.estimate.value,
+                    error: sum.error + interval.estimate.error,
+                })
+        };
+
+        while intervals.len() < self.max_intervals && total(&intervals).error > self.tolerance {
+            let (worst, _) = intervals.iter().enumerate().fold(
+                (0, Float::ZERO),
+                |(worst, error), (i, interval)| {
+                    if interval.estimate.error > error {
+                        (i, interval.estimate.error)
+                    } else {
+                        (worst, error)
+                    }
+                },
+            );
+
+            let interval = intervals.swap_remove(worst);
+            let middle = (interval.from + interval.to) / Float::from(2.0);
+            for (from, to) in [(interval.from, middle), (middle, interval.to)] {
+                intervals.push(Interval {
+                    from,
+                    to,
+                    estimate: Self::rule(from, to, &func),
+                });
+            }
+        }
+
+        total(&intervals)
+    }
+}
+
+macro_rules! quadrature_integrator {
+    ($method:ty) => {
+        impl Integrator for $method {
+            type Input = Float;
+            type Output = Float;
+
+            fn integrate<F>(from: Self::Input, to: Self::Input, func: F) -> Self::Output
+            where
+                F: Fn(Self::Input) -> Self::Output,
+            {
+                <$method>::default().estimate(from, to, func).value
+            }
+        }
+    };
+}
+
+quadrature_integrator!(AdaptiveSimpson);
+quadrature_integrator!(Romberg);
+quadrature_integrator!(GaussKronrod);
+
+#[cfg(test)]
+mod quadrature_tests {
+    use super::*;
+    use crate::constant::E;
+    use crate::constant::PI;
+    use crate::traits::Approximately;
+
+    const TOLERANCE: f64 = 1e-9;
+
+    fn check<Q: Quadrature>(method: &Q) {
+        let cubic = method.estimate(Float::from(-1.0), Float::from(2.0), |x| x * x * x - x);
+        assert!(cubic
+            .value
+            .approximately(Float::from(2.25), Float::from(TOLERANCE)));
+
+        let sine = method.estimate(Float::ZERO, PI, |x| x.sin());
+        assert!(sine
+            .value
+            .approximately(Float::from(2.0), Float::from(TOLERANCE)));
+        assert!(sine.error <= Float::from(TOLERANCE));
+
+        let exponential = method.estimate(Float::ZERO, Float::ONE, |x| x.exp());
+        assert!(exponential
+            .value
+            .approximately(E - Float::ONE, Float::from(TOLERANCE)));
+
+        let reversed = method.estimate(PI, Float::ZERO, |x| x.sin());
+        assert!(reversed
+            .value
+            .approximately(Float::from(-2.0), Float::from(TOLERANCE)));
+    }
+
+    fn check_improper<Q: Quadrature>(method: &Q, tolerance: f64) {
+        let tolerance = Float::from(tolerance);
+
+        let decay = method.estimate_improper(Some(Float::ZERO), None, |x| (-x).exp());
+        assert!(decay.value.approximately(Float::ONE, tolerance));
+
+        let growth = method.estimate_improper(None, Some(Float::ZERO), |x| x.exp());
+        assert!(growth.value.approximately(Float::ONE, tolerance));
+
+        let gaussian = method.estimate_improper(None, None, |x| (-x * x).exp());
+        assert!(gaussian.value.approximately(PI.sqrt(), tolerance));
+
+        let finite = method.estimate_improper(Some(Float::ZERO), Some(PI), |x| x.sin());
+        assert!(finite.value.approximately(Float::from(2.0), tolerance));
+    }
+
+    #[test]
+    fn adaptive_simpson() {
+        check(&AdaptiveSimpson::default());
+    }
+
+    #[test]
+    fn romberg() {
+        check(&Romberg::default());
+    }
+
+    #[test]
+    fn gauss_kronrod() {
+        check(&GaussKronrod::default());
+    }
+
+    #[test]
+    fn gauss_kronrod_is_exact_for_polynomials() {
+        // A single K15 rule integrates polynomials up to degree 22 exactly, so no subdivision is needed
+        let estimate =
+            GaussKronrod::new(TOLERANCE, 1).estimate(Float::ZERO, Float::ONE, |x| x.powi(10));
+        assert!(estimate
+            .value
+            .approximately(Float::from(1.0 / 11.0), Float::from(1e-14)));
+        assert!(estimate.error < Float::from(1e-14));
+    }
+
+    #[test]
+    fn error_estimate_bounds_actual_error() {
+        let estimate =
+            GaussKronrod::new(0.0, 1).estimate(Float::ZERO, Float::from(10.0), |x| x.sin());
+        let exact = Float::ONE - Float::from(10.0).cos();
+        assert!(estimate.error > Float::ZERO);
+        assert!((estimate.value - exact).abs() <= estimate.error);
+    }
+
+    #[test]
+    fn endpoint_singularity() {
+        let method = GaussKronrod::default();
+        let estimate = method.estimate(Float::ZERO, Float::ONE, |x| Float::ONE / x.sqrt());
+        assert!(estimate
+            .value
+            .approximately(Float::from(2.0), Float::from(1e-8)));
+    }
+
+    #[test]
+    fn improper_integrals() {
+        check_improper(&GaussKronrod::default(), 1e-9);
+        check_improper(&AdaptiveSimpson::default(), 1e-8);
+        check_improper(&Romberg::default(), 1e-6);
+    }
+
+    #[test]
+    fn integrator_trait() {
+        let square = |x: Float| x * x;
+        let expected = Float::from(9.0);
+        let epsilon = Float::from(TOLERANCE);
+        assert!(
+            AdaptiveSimpson::integrate(Float::ZERO, Float::from(3.0), square)
+                .approximately(expected, epsilon)
+        );
+        assert!(Romberg::integrate(Float::ZERO, Float::from(3.0), square)
+            .approximately(expected, epsilon));
+        assert!(
+            GaussKronrod::integrate(Float::ZERO, Float::from(3.0), square)
+                .approximately(expected, epsilon)
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//!
+//! Symplectic integrators for Hamiltonian systems
+//!
+//! These methods advance a [Phase] using an acceleration function `f(t, x) -> a` that depends
+//! only on position, as is the case for gravity, springs and other conservative forces.
+//! They preserve the geometric structure of the motion, so the energy error stays bounded
+//! over very long simulations instead of drifting like it does with [super::Euler] or
+//! [super::RungeKutta4]. See also [super::SemiImplicitEuler]
+//!
+
+use std::marker::PhantomData;
+
+use crate::integrate::Phase;
+use crate::integrate::State;
+use crate::integrate::Stepper;
+use crate::Float;
+
+/// The velocity Verlet method
+///
+/// Second order accurate, evaluating the acceleration twice per step. Position and
+/// velocity are both known at the end of every step
+pub struct VelocityVerlet<S: State, F = fn(Float, &S) -> <S as State>::Derivative> {
+    acceleration: F,
+    _state: PhantomData<S>,
+}
+
+impl<S, F> VelocityVerlet<S, F>
+where
+    S: State,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    /// Constructs a new [VelocityVerlet] stepper from an acceleration function `f(t, x) -> a`
+    pub fn new(acceleration: F) -> Self {
+        Self {
+            acceleration,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<S, F> Stepper<Phase<S>> for VelocityVerlet<S, F>
+where
+    S: State,
+    S::Derivative: State<Derivative = S::Derivative>,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    fn step(&self, state: Phase<S>, t: Float, dt: Float) -> Phase<S> {
+        let half = dt / Float::from(2.0);
+
+        let a0 = (self.acceleration)(t, &state.position);
+        let velocity = state.velocity.advance(&a0, half);
+        let position = state.position.advance(&velocity, dt);
+
+        let a1 = (self.acceleration)(t + dt, &position);
+        let velocity = velocity.advance(&a1, half);
+        Phase { position, velocity }
+    }
+}
+
+/// The leapfrog (drift-kick-drift) method
+///
+/// Second order accurate like [VelocityVerlet], but evaluates the acceleration only once
+/// per step, at the midpoint position
+pub struct Leapfrog<S: State, F = fn(Float, &S) -> <S as State>::Derivative> {
+    acceleration: F,
+    _state: PhantomData<S>,
+}
+
+impl<S, F> Leapfrog<S, F>
+where
+    S: State,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    /// Constructs a new [Leapfrog] stepper from an acceleration function `f(t, x) -> a`
+    pub fn new(acceleration: F) -> Self {
+        Self {
+            acceleration,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<S, F> Stepper<Phase<S>> for Leapfrog<S, F>
+where
+    S: State,
+    S::Derivative: State<Derivative = S::Derivative>,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    fn step(&self, state: Phase<S>, t: Float, dt: Float) -> Phase<S> {
+        let half = dt / Float::from(2.0);
+
+        let midpoint = state.position.advance(&state.velocity, half);
+        let a = (self.acceleration)(t + half, &midpoint);
+        let velocity = state.velocity.advance(&a, dt);
+        let position = midpoint.advance(&velocity, half);
+        Phase { position, velocity }
+    }
+}
+
+/// Yoshida's fourth order symplectic method
+///
+/// Composes three leapfrog substeps with carefully chosen weights, one of which steps
+/// backwards in time, to cancel the second order error terms. Evaluates the acceleration
+/// three times per step
+pub struct Yoshida4<S: State, F = fn(Float, &S) -> <S as State>::Derivative> {
+    acceleration: F,
+    drift: [Float; 4],
+    kick: [Float; 3],
+    _state: PhantomData<S>,
+}
+
+impl<S, F> Yoshida4<S, F>
+where
+    S: State,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    /// Constructs a new [Yoshida4] stepper from an acceleration function `f(t, x) -> a`
+    pub fn new(acceleration: F) -> Self {
+        let cbrt_2 = f64::cbrt(2.0);
+        let w1 = 1.0 / (2.0 - cbrt_2);
+        let w0 = -cbrt_2 * w1;
+
+        let c1 = Float::from(w1 / 2.0);
+        let c2 = Float::from((w0 + w1) / 2.0);
+        let d1 = Float::from(w1);
+        let d2 = Float::from(w0);
+
+        Self {
+            acceleration,
+            drift: [c1, c2, c2, c1],
+            kick: [d1, d2, d1],
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<S, F> Stepper<Phase<S>> for Yoshida4<S, F>
+where
+    S: State,
+    S::Derivative: State<Derivative = S::Derivative>,
+    F: Fn(Float, &S) -> S::Derivative,
+{
+    fn step(&self, state: Phase<S>, t: Float, dt: Float) -> Phase<S> {
+        let Phase {
+            mut position,
+            mut velocity,
+        } = state;
+
+        let mut time = t;
+        for i in 0..3 {
+            let drift = self.drift[i] * dt;
+            position = position.advance(&velocity, drift);
+            time += drift;
+
+            let a = (self.acceleration)(time, &position);
+            velocity = velocity.advance(&a, self.kick[i] * dt);
+        }
+        position = position.advance(&velocity, self.drift[3] * dt);
+
+        Phase { position, velocity }
+    }
+}
+
+#[cfg(test)]
+mod symplectic_tests {
+    use super::*;
+    use crate::constant::PI;
+    use crate::integrate::Euler;
+    use crate::traits::Approximately;
+    use crate::traits::FloatExt;
+    use crate::Point;
+    use crate::Vector;
+
+    fn spring(_t: Float, x: &Point) -> Vector {
+        -x.as_vector()
+    }
+
+    fn gravity(_t: Float, x: &Point) -> Vector {
+        let r = x.as_vector();
+        let r_sq = r.length_sq();
+        r * (-Float::ONE / (r_sq * r_sq.sqrt()))
+    }
+
+    fn spring_energy(state: &Phase<Point>) -> Float {
+        (state.position.as_vector().length_sq() + state.velocity.length_sq()) / Float::from(2.0)
+    }
+
+    fn orbit_energy(state: &Phase<Point>) -> Float {
+        state.velocity.length_sq() / Float::from(2.0)
+            - Float::ONE / state.position.as_vector().length()
+    }
+
+    fn orbit_start() -> Phase<Point> {
+        // An eccentric orbit, with GM = 1
+        Phase::new(Point::new(1.0, 0.0, 0.0), Vector::new(0.0, 1.2, 0.0))
+    }
+
+    /// Runs `steps` steps, returning the largest absolute change in energy along the way
+    fn max_energy_error<T, E>(
+        stepper: &T,
+        start: Phase<Point>,
+        dt: Float,
+        steps: usize,
+        energy: E,
+    ) -> Float
+    where
+        T: Stepper<Phase<Point>>,
+        E: Fn(&Phase<Point>) -> Float,
+    {
+        let initial = energy(&start);
+        let mut state = start;
+        let mut max_error = Float::ZERO;
+        for i in 0..steps {
+            state = stepper.step(state, dt * Float::from(i as f64), dt);
+            max_error = Float::max(max_error, (energy(&state) - initial).abs());
+        }
+        max_error
+    }
+
+    #[test]
+    fn velocity_verlet_spring_energy() {
+        let start = Phase::new(Point::new(1.0, 0.0, 0.0), Vector::new(0.0, 0.5, 0.0));
+        let verlet = VelocityVerlet::new(spring);
+        let error = max_energy_error(&verlet, start, Float::from(0.05), 20_000, spring_energy);
+        assert!(error < Float::from(1e-3));
+    }
+
+    #[test]
+    fn leapfrog_spring_energy() {
+        let start = Phase::new(Point::new(1.0, 0.0, 0.0), Vector::new(0.0, 0.5, 0.0));
+        let leapfrog = Leapfrog::new(spring);
+        let error = max_energy_error(&leapfrog, start, Float::from(0.05), 20_000, spring_energy);
+        assert!(error < Float::from(1e-3));
+    }
+
+    #[test]
+    fn yoshida_spring_energy() {
+        let start = Phase::new(Point::new(1.0, 0.0, 0.0), Vector::new(0.0, 0.5, 0.0));
+        let yoshida = Yoshida4::new(spring);
+        let error = max_energy_error(&yoshida, start, Float::from(0.05), 20_000, spring_energy);
+        assert!(error < Float::from(1e-5));
+    }
+
+    #[test]
+    fn orbit_energy_is_bounded() {
+        let dt = Float::from(0.01);
+        let steps = 50_000;
+
+        let verlet = max_energy_error(
+            &VelocityVerlet::new(gravity),
+            orbit_start(),
+            dt,
+            steps,
+            orbit_energy,
+        );
+        let leapfrog = max_energy_error(
+            &Leapfrog::new(gravity),
+            orbit_start(),
+            dt,
+            steps,
+            orbit_energy,
+        );
+        let yoshida = max_energy_error(
+            &Yoshida4::new(gravity),
+            orbit_start(),
+            dt,
+            steps,
+            orbit_energy,
+        );
+
+        assert!(verlet < Float::from(1e-3));
+        assert!(leapfrog < Float::from(1e-3));
+        assert!(yoshida < Float::from(1e-5));
+    }
+
+    #[test]
+    fn explicit_euler_drifts() {
+        // The same orbit with a non-symplectic method gains energy without bound
+        let euler = Euler::new(|t: Float, s: &Phase<Point>| Phase {
+            position: s.velocity,
+            velocity: gravity(t, &s.position),
+        });
+        let error = max_energy_error(
+            &euler,
+            orbit_start(),
+            Float::from(0.01),
+            50_000,
+            orbit_energy,
+        );
+        assert!(error > Float::from(0.1));
+    }
+
+    #[test]
+    fn yoshida_is_fourth_order() {
+        let start = Phase::new(Point::new(1.0, 0.0, 0.0), Vector::new(0.0, 0.0, 0.0));
+        let exact = Point::new(Float::from(2.0).cos(), Float::ZERO, Float::ZERO);
+        let yoshida = Yoshida4::new(spring);
+
+        let coarse = yoshida.solve(start, Float::ZERO, Float::from(2.0), 20);
+        let fine = yoshida.solve(start, Float::ZERO, Float::from(2.0), 40);
+        let ratio = (coarse.position - exact).length() / (fine.position - exact).length();
+        assert!(ratio > Float::from(14.0) && ratio < Float::from(18.0));
+    }
+
+    #[test]
+    fn full_period() {
+        let start = Phase::new(Point::new(1.0, 0.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        let period = Float::from(2.0) * PI;
+
+        let verlet = VelocityVerlet::new(spring).solve(start, Float::ZERO, period, 1000);
+        let leapfrog = Leapfrog::new(spring).solve(start, Float::ZERO, period, 1000);
+        let yoshida = Yoshida4::new(spring).solve(start, Float::ZERO, period, 1000);
+
+        assert!(verlet
+            .position
+            .approximately(&start.position, Float::from(1e-4)));
+        assert!(leapfrog
+            .position
+            .approximately(&start.position, Float::from(1e-4)));
+        assert!(yoshida
+            .position
+            .approximately(&start.position, Float::from(1e-9)));
+        assert!(yoshida
+            .velocity
+            .approximately(start.velocity, Float::from(1e-9)));
+    }
+}
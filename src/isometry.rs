@@ -0,0 +1,316 @@
+//!
+//! Rigid transforms
+//!
+//! An [Isometry] is a rotation followed by a translation, the compact alternative to a
+//! [Matrix] for transforms that never scale or shear. It is the same rigid motion as a
+//! [Motor], which is used for dual quaternion blending
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::matrix::Matrix;
+use crate::pga::Motor;
+use crate::rotor::Rotor;
+use crate::traits::Zero;
+use crate::Approximately;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+use std::ops::Mul;
+
+/// How far the scale of a [Matrix] may stray from one for [Isometry::from_matrix] to accept it
+const UNIT_SCALE_TOLERANCE: f64 = 1e-9;
+
+/// A rigid transform, a rotation about the origin followed by a translation
+///
+/// The product `a * b` is the transform that applies `b` first and then `a`
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Isometry {
+    pub rotation: Rotor,
+    pub translation: Vector,
+}
+
+impl Default for Isometry {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Isometry {
+    /// Constructs a new [Isometry] that rotates by `rotation` and then translates by `translation`
+    pub fn new(rotation: Rotor, translation: Vector) -> Self {
+        Self {
+            rotation,
+            translation,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(Rotor::identity(), Vector::zero())
+    }
+
+    /// Constructs a new [Isometry] that only rotates
+    pub fn from_rotation(rotation: Rotor) -> Self {
+        Self::new(rotation, Vector::zero())
+    }
+
+    /// Constructs a new [Isometry] that only translates
+    pub fn from_translation(translation: Vector) -> Self {
+        Self::new(Rotor::identity(), translation)
+    }
+
+    /// Constructs a new [Isometry] from a rigid transform [Matrix] laid out as by
+    /// [Isometry::to_matrix], see [Matrix::decompose_column_vector]
+    ///
+    /// Returns `None` if the matrix scales, shears or can not be decomposed
+    pub fn from_matrix(matrix: &Matrix) -> Option<Self> {
+        let decomposition = matrix.decompose_column_vector()?;
+        let unit = Vector::new(1.0, 1.0, 1.0);
+        decomposition
+            .scale
+            .approximately(unit, Float::from(UNIT_SCALE_TOLERANCE))
+            .then(|| Self::new(decomposition.orientation, decomposition.translation))
+    }
+
+    /// Returns the inverse of this [Isometry], which undoes its transform
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.reversed();
+        Self::new(rotation, -self.translation.rotated_by(&rotation))
+    }
+
+    /// Inverts this [Isometry] in place
+    pub fn invert(&mut self) {
+        *self = self.inverse();
+    }
+
+    /// Applies this [Isometry] to a [Point], rotating it about the origin and then translating it
+    pub fn transform_point(&self, point: &Point) -> Point {
+        Point::from(point.as_vector().rotated_by(&self.rotation) + self.translation)
+    }
+
+    /// Applies this [Isometry] to a direction [Vector], which is only rotated
+    pub fn transform_vector(&self, vector: &Vector) -> Vector {
+        vector.rotated_by(&self.rotation)
+    }
+
+    /// Returns the [Matrix] of this [Isometry], which transforms column vectors with the
+    /// translation in column 3 like [Matrix::from_translation]
+    ///
+    /// `&isometry.to_matrix() * &point` is the same as [Isometry::transform_point], and the
+    /// matrix can be passed straight to [crate::aabb::Aabb::transformed]
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_translation_and_orientation(Point::from(self.translation), self.rotation)
+            .transposed()
+    }
+
+    /// Blends several [Isometry]s by dual quaternion linear blending, see [Motor::blend]
+    ///
+    /// This is the usual way to blend bone transforms when skinning, and unlike blending
+    /// matrices it always gives a rigid transform. Returns `None` if `weighted` is empty or
+    /// the weights cancel out
+    pub fn blend(weighted: &[(Isometry, Float)]) -> Option<Self> {
+        let motors: Vec<(Motor, Float)> = weighted
+            .iter()
+            .map(|(isometry, weight)| (Motor::from(*isometry), *weight))
+            .collect();
+        Motor::blend(&motors).map(Self::from)
+    }
+}
+
+impl Mul for Isometry {
+    type Output = Isometry;
+
+    fn mul(self, rhs: Isometry) -> Self::Output {
+        Isometry::new(
+            self.rotation * rhs.rotation,
+            self.transform_vector(&rhs.translation) + self.translation,
+        )
+    }
+}
+
+impl From<Isometry> for Matrix {
+    fn from(isometry: Isometry) -> Self {
+        isometry.to_matrix()
+    }
+}
+
+impl From<Isometry> for Motor {
+    fn from(isometry: Isometry) -> Self {
+        Motor::from_rotor_and_translation(isometry.rotation, isometry.translation)
+    }
+}
+
+impl From<Motor> for Isometry {
+    fn from(motor: Motor) -> Self {
+        Isometry::new(motor.rotor(), motor.translation())
+    }
+}
+
+impl Approximately for Isometry {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        self.rotation.approximately(other.rotation, epsilon)
+            && self.translation.approximately(other.translation, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod isometry_tests {
+    use super::*;
+    use crate::constant::PI;
+
+    const EPSILON: Float = 1e-9;
+
+    fn isometry() -> Isometry {
+        Isometry::new(
+            Rotor::from_axis_angle(Vector::new(1.0, 2.0, -1.0), 1.3),
+            Vector::new(4.0, -2.0, 0.5),
+        )
+    }
+
+    #[test]
+    fn transform() {
+        let isometry = Isometry::new(
+            Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0),
+            Vector::new(1.0, 0.0, 0.0),
+        );
+        let point = isometry.transform_point(&Point::new(1.0, 0.0, 3.0));
+        assert!(point.approximately(&Point::new(1.0, 1.0, 3.0), EPSILON));
+
+        let vector = isometry.transform_vector(&Vector::unit_x());
+        assert!(vector.approximately(Vector::unit_y(), EPSILON));
+    }
+
+    #[test]
+    fn composition() {
+        let a = isometry();
+        let b = Isometry::new(
+            Rotor::from_axis_angle(Vector::unit_y(), -0.4),
+            Vector::new(0.0, 1.0, 2.0),
+        );
+        let p = Point::new(0.3, -1.0, 2.0);
+        let sequential = a.transform_point(&b.transform_point(&p));
+        assert!((a * b)
+            .transform_point(&p)
+            .approximately(&sequential, EPSILON));
+    }
+
+    #[test]
+    fn inverse() {
+        let isometry = isometry();
+        let p = Point::new(1.0, 2.0, 3.0);
+        let back = isometry
+            .inverse()
+            .transform_point(&isometry.transform_point(&p));
+        assert!(back.approximately(&p, EPSILON));
+        assert!((isometry * isometry.inverse()).approximately(Isometry::identity(), EPSILON));
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        let isometry = isometry();
+        let matrix = Matrix::from(isometry);
+        let recovered = Isometry::from_matrix(&matrix).unwrap();
+        assert!(recovered
+            .translation
+            .approximately(isometry.translation, EPSILON));
+
+        let v = Vector::new(0.2, -0.4, 0.9);
+        assert!(recovered
+            .transform_vector(&v)
+            .approximately(isometry.transform_vector(&v), EPSILON));
+
+        let scaled = Matrix::from_translation_orientation_and_scale(
+            isometry.translation,
+            isometry.rotation,
+            Vector::new(2.0, 2.0, 2.0),
+        )
+        .transposed();
+        assert!(Isometry::from_matrix(&scaled).is_none());
+    }
+
+    #[test]
+    fn matrix_round_trip_without_translation() {
+        let rotation = Rotor::from_axis_angle(Vector::unit_z(), 0.7);
+        let isometry = Isometry::from_rotation(rotation);
+        for matrix in [Matrix::from(isometry), Matrix::from(rotation)] {
+            let recovered = Isometry::from_matrix(&matrix).unwrap();
+            assert!(recovered.approximately(isometry, EPSILON));
+
+            let p = Point::new(1.0, 0.0, 0.0);
+            let expected = p.as_vector().rotated_by(&rotation);
+            assert!((&matrix * &p).approximately(&Point::from(expected), EPSILON));
+            assert!(expected.y > 0.0);
+        }
+    }
+
+    #[test]
+    fn matrix_transforms_points() {
+        let isometry = isometry();
+        let matrix = Matrix::from(isometry);
+        for p in [
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(-2.0, 0.5, 3.0),
+            Point::origin(),
+        ] {
+            assert!((&matrix * &p).approximately(&isometry.transform_point(&p), EPSILON));
+        }
+    }
+
+    #[test]
+    fn motor_round_trip() {
+        let isometry = isometry();
+        let motor = Motor::from(isometry);
+        let p = Point::new(-1.0, 0.5, 2.0);
+        assert!(motor
+            .transform(&p)
+            .approximately(&isometry.transform_point(&p), EPSILON));
+        assert!(Isometry::from(motor).approximately(isometry, EPSILON));
+    }
+
+    #[test]
+    fn blend() {
+        let a = Isometry::from_translation(Vector::new(2.0, 0.0, 0.0));
+        let b = Isometry::new(
+            Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0),
+            Vector::new(0.0, 2.0, 0.0),
+        );
+
+        let first = Isometry::blend(&[(a, 1.0), (b, 0.0)]).unwrap();
+        assert!(first.approximately(a, EPSILON));
+
+        // Blending rotations about the origin halves the angle
+        let c = Isometry::from_rotation(Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0));
+        let halfway = Isometry::blend(&[(Isometry::identity(), 0.5), (c, 0.5)]).unwrap();
+        let expected = Rotor::from_axis_angle(Vector::unit_z(), PI / 4.0);
+        assert!(halfway.rotation.approximately(expected, EPSILON));
+        assert!(halfway.translation.approximately(Vector::zero(), EPSILON));
+
+        // Pure translations blend linearly
+        let d = Isometry::from_translation(Vector::new(0.0, 4.0, 0.0));
+        let mixed = Isometry::blend(&[(a, 0.25), (d, 0.75)]).unwrap();
+        assert!(mixed
+            .translation
+            .approximately(Vector::new(0.5, 3.0, 0.0), EPSILON));
+
+        // The result is always rigid
+        let rigid = Isometry::blend(&[(a, 0.3), (b, 0.7)]).unwrap();
+        assert!(rigid.rotation.magnitude().approximately(1.0, EPSILON));
+
+        assert!(Isometry::blend(&[]).is_none());
+    }
+
+    #[test]
+    fn blend_takes_shortest_rotation() {
+        let rotation = Rotor::from_axis_angle(Vector::unit_x(), 0.5);
+        let a = Isometry::from_rotation(rotation);
+        let flipped = Rotor::new(-rotation.b, -rotation.s);
+        let b = Isometry::from_rotation(flipped);
+
+        let blended = Isometry::blend(&[(a, 0.5), (b, 0.5)]).unwrap();
+        let v = Vector::unit_y();
+        assert!(blended
+            .transform_vector(&v)
+            .approximately(v.rotated_by(&rotation), EPSILON));
+    }
+}
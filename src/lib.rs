@@ -120,22 +120,33 @@ pub type Float = precision::types::FType;
 pub type Int = precision::types::IType;
 pub type Unsigned = precision::types::UType;
 
+pub mod aabb;
 pub mod bivec;
+pub mod camera;
 pub mod circle;
 pub mod constant;
 pub mod fixed;
+pub mod frustum;
+pub mod gpu;
 pub mod integrate;
+pub mod isometry;
 pub mod line;
 pub mod matrix;
+pub mod multivector;
 pub mod percent;
+pub mod pga;
 pub mod plane;
 pub mod point;
+pub mod ray;
 pub mod rotor;
 pub mod segment;
 pub mod shape;
 pub mod sphere;
 pub mod traits;
+pub mod transform;
+pub mod triangle;
 pub mod vec;
+pub mod viewport;
 
 pub use point::Point;
 pub use traits::*;
@@ -158,6 +169,7 @@ impl One for Float {
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_conversion, clippy::legacy_numeric_constants)]
 mod equality_tests {
     use super::*;
     use std::f64::INFINITY;
@@ -3,11 +3,15 @@
 //!
 //! This Matrix code takes a Vulkan-centric opinion. Graphics-related methods like perspective(...)
 //! are designed to work with the Vulkan graphics library and may not work as expected with other libraries
+//!
+//! The projection constructors taking a [ClipSpace], like [Matrix::perspective_fov], can target
+//! other graphics libraries by picking the matching convention per call
 
 use std::ops::Index;
 use std::ops::IndexMut;
 use std::ops::Mul;
 
+use crate::bivec::Bivector;
 use crate::rotor::Rotor;
 use crate::traits::Approximately;
 use crate::traits::FloatExt;
@@ -19,6 +23,192 @@ use crate::Vector;
 
 pub const MATRIX_4X4: usize = 4usize;
 
+/// The translation, orientation and scale making up an affine [Matrix], see [Matrix::decompose]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decomposition {
+    pub translation: Vector,
+    pub orientation: Rotor,
+    pub scale: Vector,
+}
+
+impl Decomposition {
+    /// Recomposes the [Matrix] this was decomposed from, in the row-vector layout of
+    /// [Matrix::decompose]. Transpose it to recompose a [Matrix::decompose_column_vector]
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_translation_orientation_and_scale(
+            self.translation,
+            self.orientation,
+            self.scale,
+        )
+    }
+}
+
+/// The range normalized device coordinate depth is mapped into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthRange {
+    /// Depth in `[0, 1]`, as used by Vulkan, Direct3D and WebGPU
+    ZeroToOne,
+    /// Depth in `[-1, 1]`, as used by OpenGL
+    NegativeOneToOne,
+}
+
+/// The handedness of view space, which decides the direction the camera looks along
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handedness {
+    /// The camera looks down -Z with +Y up, matching [Matrix::look_at]
+    Right,
+    /// The camera looks down +Z with +Y up
+    Left,
+}
+
+/// The clip space convention a projection [Matrix] targets
+///
+/// The presets cover the common graphics libraries, and the `with_*` methods adjust a
+/// preset, e.g. `ClipSpace::VULKAN.with_reversed_z(true)` for a reversed depth buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipSpace {
+    pub depth: DepthRange,
+    pub handedness: Handedness,
+    /// Normalized device coordinate +Y points down the screen when set
+    pub y_flip: bool,
+    /// The near plane maps to the far end of the depth range and the far plane to the near end
+    pub reversed_z: bool,
+}
+
+impl ClipSpace {
+    /// Vulkan: `[0, 1]` depth with +Y down the screen, from a right-handed view space
+    pub const VULKAN: ClipSpace = ClipSpace {
+        depth: DepthRange::ZeroToOne,
+        handedness: Handedness::Right,
+        y_flip: true,
+        reversed_z: false,
+    };
+
+    /// OpenGL: `[-1, 1]` depth with +Y up the screen, from a right-handed view space
+    pub const OPENGL: ClipSpace = ClipSpace {
+        depth: DepthRange::NegativeOneToOne,
+        handedness: Handedness::Right,
+        y_flip: false,
+        reversed_z: false,
+    };
+
+    /// Direct3D: `[0, 1]` depth with +Y up the screen, from a left-handed view space
+    pub const DIRECT3D: ClipSpace = ClipSpace {
+        depth: DepthRange::ZeroToOne,
+        handedness: Handedness::Left,
+        y_flip: false,
+        reversed_z: false,
+    };
+
+    /// WebGPU: `[0, 1]` depth with +Y up the screen, from a right-handed view space
+    pub const WEBGPU: ClipSpace = ClipSpace {
+        depth: DepthRange::ZeroToOne,
+        handedness: Handedness::Right,
+        y_flip: false,
+        reversed_z: false,
+    };
+
+    pub fn with_depth(self, depth: DepthRange) -> Self {
+        Self { depth, ..self }
+    }
+
+    pub fn with_handedness(self, handedness: Handedness) -> Self {
+        Self { handedness, ..self }
+    }
+
+    pub fn with_y_flip(self, y_flip: bool) -> Self {
+        Self { y_flip, ..self }
+    }
+
+    pub fn with_reversed_z(self, reversed_z: bool) -> Self {
+        Self { reversed_z, ..self }
+    }
+
+    /// Returns the normalized device coordinate depths of the near and far planes
+    pub fn depth_bounds(&self) -> (Float, Float) {
+        let (min, max) = match self.depth {
+            DepthRange::ZeroToOne => (Float::ZERO, Float::ONE),
+            DepthRange::NegativeOneToOne => (-Float::ONE, Float::ONE),
+        };
+        if self.reversed_z {
+            (max, min)
+        } else {
+            (min, max)
+        }
+    }
+
+    /// Returns the sign of view space z in front of the camera
+    fn forward_sign(&self) -> Float {
+        match self.handedness {
+            Handedness::Right => -Float::ONE,
+            Handedness::Left => Float::ONE,
+        }
+    }
+
+    /// Returns the sign applied to normalized device coordinate y
+    fn y_sign(&self) -> Float {
+        if self.y_flip {
+            -Float::ONE
+        } else {
+            Float::ONE
+        }
+    }
+}
+
+impl Default for ClipSpace {
+    fn default() -> Self {
+        Self::VULKAN
+    }
+}
+
+/// Finds the [Rotor] which rotates the x, y and z axes onto the orthonormal, right-handed
+/// basis vectors `x`, `y` and `z`
+pub(crate) fn orientation_from_basis(x: &Vector, y: &Vector, z: &Vector) -> Rotor {
+    // Extract a quaternion from the rotation matrix with the basis vectors as its
+    // columns, branching on the largest component to keep the division well conditioned
+    let trace = x.x + y.y + z.z;
+    let one = Float::one();
+    let two = Float::from(2.0);
+    let quarter = Float::from(0.25);
+
+    let (w, i, j, k) = if trace > Float::zero() {
+        let s = (trace + one).sqrt() * two;
+        (
+            quarter * s,
+            (y.z - z.y) / s,
+            (z.x - x.z) / s,
+            (x.y - y.x) / s,
+        )
+    } else if x.x > y.y && x.x > z.z {
+        let s = (one + x.x - y.y - z.z).sqrt() * two;
+        (
+            (y.z - z.y) / s,
+            quarter * s,
+            (y.x + x.y) / s,
+            (z.x + x.z) / s,
+        )
+    } else if y.y > z.z {
+        let s = (one + y.y - x.x - z.z).sqrt() * two;
+        (
+            (z.x - x.z) / s,
+            (y.x + x.y) / s,
+            quarter * s,
+            (z.y + y.z) / s,
+        )
+    } else {
+        let s = (one + z.z - x.x - y.y).sqrt() * two;
+        (
+            (x.y - y.x) / s,
+            (z.x + x.z) / s,
+            (z.y + y.z) / s,
+            quarter * s,
+        )
+    };
+
+    // The quaternion axis (i, j, k) maps to the plane dual to it, see [Bivector::from_axis_vector]
+    Rotor::new(Bivector::new(-k, j, -i), w).normalized()
+}
+
 /// A 4x4 Matrix
 #[derive(Debug, Clone)]
 pub struct Matrix {
@@ -92,10 +282,27 @@ impl Matrix {
         matrix[3][0] = t.x;
         matrix[3][1] = t.y;
         matrix[3][2] = t.z;
-        matrix[3][3] = Float::zero();
+        matrix[3][3] = Float::one();
         matrix
     }
     
+    /// Construct a new [Matrix] from a translation [Vector], an orientation [Rotor] and a
+    /// per-axis scale [Vector], applying the scale first and the translation last
+    ///
+    /// The resulting [Matrix] has the same layout as [Matrix::from_translation_and_orientation],
+    /// with each basis row multiplied by the matching scale component
+    #[inline]
+    pub fn from_translation_orientation_and_scale(translation: Vector, orientation: Rotor, scale: Vector) -> Self {
+        let mut matrix = Self::from_translation_and_orientation(Point::from(translation), orientation);
+        let scale = [scale.x, scale.y, scale.z];
+        for (row, factor) in scale.iter().enumerate() {
+            for col in 0..3 {
+                matrix[row][col] *= *factor;
+            }
+        }
+        matrix
+    }
+
     /// Construct a new translation [Matrix] from a [Vector]
     ///
     /// The resulting [Matrix] has the form:
@@ -215,8 +422,8 @@ impl Matrix {
         let s = f.cross(&up).normalized();
         let u = s.cross(&f).normalized();
 
-        let eds = eye.as_vector().dot(&s);
-        let edu = eye.as_vector().dot(&u);
+        let eds = -eye.as_vector().dot(&s);
+        let edu = -eye.as_vector().dot(&u);
         let edf = eye.as_vector().dot(&f);
         
         Matrix::new([
@@ -319,6 +526,92 @@ impl Matrix {
         }
     }
 
+    /// Construct a perspective projection [Matrix] from a vertical field of view in radians and
+    /// an aspect ratio of width over height
+    ///
+    /// Like [Matrix::perspective] it projects column vectors, see `&Matrix * &Point`. Pass
+    /// [Float::INFINITY] as `far` for an infinite far plane, see [Matrix::perspective_infinite]
+    /// ```
+    /// # use integrator::matrix::*;
+    /// # use integrator::{Approximately, Point};
+    /// let projection = Matrix::perspective_fov(1.0, 16.0 / 9.0, 0.1, 100.0, ClipSpace::OPENGL);
+    /// let near = &projection * &Point::new(0.0, 0.0, -0.1);
+    /// let far = &projection * &Point::new(0.0, 0.0, -100.0);
+    /// assert!(near.z.approximately(-1.0, 1e-9));
+    /// assert!(far.z.approximately(1.0, 1e-9));
+    /// ```
+    pub fn perspective_fov<F: Into<Float>>(fovy: F, aspect: F, near: F, far: F, clip: ClipSpace) -> Self {
+        let n = near.into();
+        let top = n * Float::tan(fovy.into() / Float::from(2.0));
+        let right = aspect.into() * top;
+        Self::frustum(-right, right, -top, top, n, far.into(), clip)
+    }
+
+    /// Construct a perspective projection [Matrix] with the far plane at infinity
+    ///
+    /// Combined with [ClipSpace::with_reversed_z] this gives the best depth precision
+    pub fn perspective_infinite<F: Into<Float>>(fovy: F, aspect: F, near: F, clip: ClipSpace) -> Self {
+        Self::perspective_fov(fovy.into(), aspect.into(), near.into(), Float::INFINITY, clip)
+    }
+
+    /// Construct an off-center perspective projection [Matrix] from the bounds of the view
+    /// volume on the near plane
+    ///
+    /// `near` and `far` are positive distances in front of the camera, and `far` may be
+    /// [Float::INFINITY]
+    pub fn frustum<F: Into<Float>>(left: F, right: F, bottom: F, top: F, near: F, far: F, clip: ClipSpace) -> Self {
+        let (l, r, b, t) = (left.into(), right.into(), bottom.into(), top.into());
+        let (n, f) = (near.into(), far.into());
+        let (near_depth, far_depth) = clip.depth_bounds();
+        let two = Float::from(2.0);
+
+        // Depth in front of the camera is d = forward * z, and w = d gives the perspective divide.
+        // NDC z = z_scale + z_offset / d, solved so the near and far planes land on their depth bounds
+        let forward = clip.forward_sign();
+        let y = clip.y_sign();
+        let (z_scale, z_offset) = if f.is_infinite() {
+            (far_depth, (near_depth - far_depth) * n)
+        } else {
+            let z_offset = (near_depth - far_depth) * n * f / (f - n);
+            (far_depth - z_offset / f, z_offset)
+        };
+
+        let zer = Float::ZERO;
+        Self {
+            elements: [
+                [two*n/(r-l), zer,           -forward*(r+l)/(r-l),   zer     ],
+                [zer,         y*two*n/(t-b), -y*forward*(t+b)/(t-b), zer     ],
+                [zer,         zer,           forward*z_scale,        z_offset],
+                [zer,         zer,           forward,                zer     ],
+            ]
+        }
+    }
+
+    /// Construct an off-center orthographic projection [Matrix] from the bounds of the view volume
+    ///
+    /// `near` and `far` are distances in front of the camera, and `far` must be finite
+    pub fn orthographic_off_center<F: Into<Float>>(left: F, right: F, bottom: F, top: F, near: F, far: F, clip: ClipSpace) -> Self {
+        let (l, r, b, t) = (left.into(), right.into(), bottom.into(), top.into());
+        let (n, f) = (near.into(), far.into());
+        let (near_depth, far_depth) = clip.depth_bounds();
+        let two = Float::from(2.0);
+
+        let forward = clip.forward_sign();
+        let y = clip.y_sign();
+        let depth_scale = (far_depth - near_depth) / (f - n);
+
+        let zer = Float::ZERO;
+        let one = Float::ONE;
+        Self {
+            elements: [
+                [two/(r-l), zer,         zer,                 -(r+l)/(r-l)             ],
+                [zer,       y*two/(t-b), zer,                 -y*(t+b)/(t-b)           ],
+                [zer,       zer,         forward*depth_scale, near_depth-depth_scale*n],
+                [zer,       zer,         zer,                 one                      ],
+            ]
+        }
+    }
+
     /// Multiplies two matrices and returns the resulting [Matrix]
     /// 
     /// ```
@@ -356,6 +649,270 @@ impl Matrix {
         }
         out
     }
+
+    /// Decomposes an affine [Matrix] into its translation, orientation and scale
+    ///
+    /// This is the inverse of [Matrix::from_translation_orientation_and_scale], and so of
+    /// [Matrix::from_translation_and_orientation], and only accepts their row-vector layout with
+    /// the translation in row 3. Use [Matrix::decompose_column_vector] for matrices which
+    /// transform column vectors, such as those from [Matrix::from_translation]
+    ///
+    /// A reflection is reported as a negative x scale. Returns `None` for matrices which are
+    /// projective, singular, or sheared, see [Matrix::has_shear]
+    ///
+    /// ```
+    /// # use integrator::matrix::*;
+    /// # use integrator::traits::Approximately;
+    /// # use integrator::rotor::Rotor;
+    /// # use integrator::Vector;
+    /// let translation = Vector::new(1.0, 2.0, 3.0);
+    /// let scale = Vector::new(2.0, 2.0, 0.5);
+    /// let m = Matrix::from_translation_orientation_and_scale(translation, Rotor::identity(), scale);
+    ///
+    /// let decomposition = m.decompose().unwrap();
+    /// assert!(decomposition.translation.approximately(translation, 1e-12));
+    /// assert!(decomposition.scale.approximately(scale, 1e-12));
+    /// ```
+    pub fn decompose(&self) -> Option<Decomposition> {
+        let (rows, translation) = self.affine_rows()?;
+
+        let mut scale = rows.map(|row| row.length());
+        if scale.contains(&Float::ZERO) || self.has_shear() {
+            return None;
+        }
+
+        let mut basis = [0, 1, 2].map(|i| rows[i] / scale[i]);
+        if basis[0].cross(&basis[1]).dot(&basis[2]) < Float::ZERO {
+            scale[0] = -scale[0];
+            basis[0] = -basis[0];
+        }
+
+        Some(Decomposition {
+            translation,
+            orientation: orientation_from_basis(&basis[0], &basis[1], &basis[2]),
+            scale: Vector::new(scale[0], scale[1], scale[2]),
+        })
+    }
+
+    /// Decomposes an affine [Matrix] which transforms column vectors, with the translation in
+    /// column 3 as in [Matrix::from_translation], see [Matrix::decompose]
+    ///
+    /// ```
+    /// # use integrator::matrix::*;
+    /// # use integrator::traits::Approximately;
+    /// # use integrator::rotor::Rotor;
+    /// # use integrator::Vector;
+    /// let rotation = Rotor::from_axis_angle(Vector::unit_z(), 0.7);
+    /// let m = Matrix::from_orientation(rotation).transposed();
+    ///
+    /// let decomposition = m.decompose_column_vector().unwrap();
+    /// let v = Vector::unit_x();
+    /// assert!(v.rotated_by(&decomposition.orientation).approximately(&m * &v, 1e-12));
+    /// ```
+    pub fn decompose_column_vector(&self) -> Option<Decomposition> {
+        self.transposed().decompose()
+    }
+
+    /// Test whether the linear part of an affine [Matrix] skews its axes, so that it can not be
+    /// represented by an orientation and a per-axis scale alone
+    ///
+    /// The matrix is read in the row-vector layout of [Matrix::decompose]. Projective and
+    /// singular matrices are not considered sheared
+    pub fn has_shear(&self) -> bool {
+        let Some((rows, _)) = self.affine_rows() else {
+            return false;
+        };
+
+        let tolerance = Float::from(1e-9);
+        let pairs = [(0, 1), (0, 2), (1, 2)];
+        pairs.iter().any(|(i, j)| {
+            let scale = rows[*i].length() * rows[*j].length();
+            scale != Float::ZERO && rows[*i].dot(&rows[*j]).abs() > tolerance * scale
+        })
+    }
+
+    /// The three basis rows and the translation of an affine [Matrix], in the layout of
+    /// [Matrix::from_translation_and_orientation]
+    fn affine_rows(&self) -> Option<([Vector; 3], Vector)> {
+        let zer = Float::zero();
+        let one = Float::one();
+        if self.col(3) != [zer, zer, zer, one] {
+            return None;
+        }
+
+        let m = self;
+        let rows = [0, 1, 2].map(|i| Vector::new(m[i][0], m[i][1], m[i][2]));
+        Some((rows, Vector::new(m[3][0], m[3][1], m[3][2])))
+    }
+
+    /// Computes the determinant of this [Matrix]
+    ///
+    /// ```
+    /// # use integrator::matrix::*;
+    /// let m = Matrix::from([
+    ///     [2.0, 0.0, 0.0, 1.0],
+    ///     [0.0, 3.0, 0.0, 2.0],
+    ///     [0.0, 0.0, 4.0, 3.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// ]);
+    /// assert_eq!(m.determinant(), 24.0);
+    /// ```
+    pub fn determinant(&self) -> Float {
+        let (s, c) = self.minors();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    /// The 2x2 minors of the top two and bottom two rows, from which both the
+    /// determinant and the adjugate are built
+    fn minors(&self) -> ([Float; 6], [Float; 6]) {
+        let m = &self.elements;
+        let s = [
+            m[0][0] * m[1][1] - m[1][0] * m[0][1],
+            m[0][0] * m[1][2] - m[1][0] * m[0][2],
+            m[0][0] * m[1][3] - m[1][0] * m[0][3],
+            m[0][1] * m[1][2] - m[1][1] * m[0][2],
+            m[0][1] * m[1][3] - m[1][1] * m[0][3],
+            m[0][2] * m[1][3] - m[1][2] * m[0][3],
+        ];
+        let c = [
+            m[2][0] * m[3][1] - m[3][0] * m[2][1],
+            m[2][0] * m[3][2] - m[3][0] * m[2][2],
+            m[2][0] * m[3][3] - m[3][0] * m[2][3],
+            m[2][1] * m[3][2] - m[3][1] * m[2][2],
+            m[2][1] * m[3][3] - m[3][1] * m[2][3],
+            m[2][2] * m[3][3] - m[3][2] * m[2][3],
+        ];
+        (s, c)
+    }
+
+    /// Computes the inverse of this [Matrix], or `None` if it is singular
+    ///
+    /// The matrix is considered singular when its determinant is negligible relative
+    /// to the magnitude of its elements
+    ///
+    /// ```
+    /// # use integrator::matrix::*;
+    /// # use integrator::traits::Approximately;
+    /// let m = Matrix::from([
+    ///     [2.0, 0.0, 0.0, 1.0],
+    ///     [0.0, 4.0, 0.0, 2.0],
+    ///     [0.0, 0.0, 8.0, 3.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// ]);
+    /// let inverse = m.try_inverse().unwrap();
+    /// assert!((&m * &inverse).approximately(Matrix::identity(), 1e-12));
+    ///
+    /// assert!(Matrix::from(0.0).try_inverse().is_none());
+    /// ```
+    pub fn try_inverse(&self) -> Option<Self> {
+        let (s, c) = self.minors();
+        let det = s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0];
+        if !self.is_invertible(det) {
+            return None;
+        }
+
+        let m = &self.elements;
+        let inv_det = Float::ONE / det;
+        let adjugate = [
+            [
+                 m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3],
+                -m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3],
+                 m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3],
+                -m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3],
+            ],
+            [
+                -m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1],
+                 m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1],
+                -m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1],
+                 m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1],
+            ],
+            [
+                 m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0],
+                -m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0],
+                 m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0],
+                -m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0],
+            ],
+            [
+                -m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0],
+                 m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0],
+                -m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0],
+                 m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0],
+            ],
+        ];
+
+        Some(Self {
+            elements: adjugate.map(|row| row.map(|e| e * inv_det)),
+        })
+    }
+
+    /// Whether a determinant of `det` is large enough, relative to the elements of this
+    /// [Matrix], for the inverse to be meaningful
+    fn is_invertible(&self, det: Float) -> bool {
+        let scale = self
+            .elements
+            .iter()
+            .flatten()
+            .fold(Float::ZERO, |max, e| Float::max(max, e.abs()));
+        let scale_sq = scale * scale;
+        det != Float::ZERO && det.abs() > Float::EPSILON * scale_sq * scale_sq
+    }
+
+    /// Computes the inverse of an affine [Matrix], or `None` if it is singular or not affine
+    ///
+    /// Much cheaper than [Matrix::try_inverse], only the upper 3x3 block is inverted. Suited to
+    /// rigid and translation-rotation-scale matrices, with the translation in either the
+    /// last column, as with [Matrix::from_translation], or the last row, as with
+    /// [Matrix::from_translation_and_orientation]
+    pub fn affine_inverse(&self) -> Option<Self> {
+        let m = &self.elements;
+        let zer = Float::ZERO;
+        let one = Float::ONE;
+
+        let column_translation = m[3] == [zer, zer, zer, one];
+        let row_translation = m[0][3] == zer && m[1][3] == zer && m[2][3] == zer && m[3][3] == one;
+        if !column_translation && !row_translation {
+            return None;
+        }
+
+        // Inverse of the 3x3 block from its cofactors
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        let c00 = cofactor(1, 2, 1, 2);
+        let c01 = -cofactor(1, 2, 0, 2);
+        let c02 = cofactor(1, 2, 0, 1);
+        let det = m[0][0] * c00 + m[0][1] * c01 + m[0][2] * c02;
+
+        let scale = (0..3)
+            .flat_map(|i| (0..3).map(move |j| (i, j)))
+            .fold(zer, |max, (i, j)| Float::max(max, m[i][j].abs()));
+        if det == zer || det.abs() <= Float::EPSILON * scale * scale * scale {
+            return None;
+        }
+
+        let inv_det = one / det;
+        let a = [
+            [c00, -cofactor(0, 2, 1, 2),  cofactor(0, 1, 1, 2)],
+            [c01,  cofactor(0, 2, 0, 2), -cofactor(0, 1, 0, 2)],
+            [c02, -cofactor(0, 2, 0, 1),  cofactor(0, 1, 0, 1)],
+        ].map(|row| row.map(|e| e * inv_det));
+
+        let mut inverse = Self::identity();
+        for i in 0..3 {
+            inverse[i][..3].copy_from_slice(&a[i]);
+        }
+
+        // For p' = Ap + t the inverse is p = A⁻¹p' - A⁻¹t, and transposed for row vectors
+        for i in 0..3 {
+            if column_translation {
+                inverse[i][3] = -(a[i][0] * m[0][3] + a[i][1] * m[1][3] + a[i][2] * m[2][3]);
+            }
+            if row_translation {
+                inverse[3][i] = -(m[3][0] * a[0][i] + m[3][1] * a[1][i] + m[3][2] * a[2][i]);
+            }
+        }
+        Some(inverse)
+    }
 }
 
 impl Approximately for Matrix {
@@ -575,6 +1132,250 @@ mod matrix_tests {
 
     use super::*;
 
+    fn general() -> Matrix {
+        Matrix::from([
+            [3.0, 2.0, -1.0, 4.0],
+            [2.0, 1.0, 5.0, 7.0],
+            [0.0, 5.0, 2.0, -6.0],
+            [-1.0, 2.0, 1.0, 0.0],
+        ])
+    }
+
+    fn trs() -> Matrix {
+        let rotation =
+            Rotor::from_angle_and_plane(0.7, crate::bivec::Bivector::new(0.36, 0.48, 0.8));
+        let scale = Matrix::from([
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 0.5, 0.0, 0.0],
+            [0.0, 0.0, 3.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        Matrix::from_translation(Vector::new(1.0, -2.0, 3.0))
+            * Matrix::from_orientation(rotation)
+            * scale
+    }
+
+    fn assert_same_rotation(a: &Rotor, b: &Rotor) {
+        for axis in [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()] {
+            assert!(axis.rotated_by(a).approximately(axis.rotated_by(b), 1e-9));
+        }
+    }
+
+    fn orientations() -> Vec<Rotor> {
+        let planes = [
+            crate::bivec::Bivector::new(1.0, 0.0, 0.0),
+            crate::bivec::Bivector::new(0.0, 1.0, 0.0),
+            crate::bivec::Bivector::new(0.0, 0.0, 1.0),
+            crate::bivec::Bivector::new(0.36, 0.48, 0.8),
+            crate::bivec::Bivector::new(-0.6, 0.0, 0.8),
+        ];
+        let angles = [0.0, 0.3, 1.5, 3.0, precise::PI, -2.2];
+        planes
+            .iter()
+            .flat_map(|plane| {
+                angles
+                    .iter()
+                    .map(|angle| Rotor::from_angle_and_plane(*angle, *plane))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decompose_orientation() {
+        for orientation in orientations() {
+            let decomposition = Matrix::from_orientation(orientation).decompose().unwrap();
+            assert_same_rotation(&decomposition.orientation, &orientation);
+            assert!(decomposition
+                .translation
+                .approximately(Vector::zero(), 1e-12));
+            assert!(decomposition
+                .scale
+                .approximately(Vector::new(1.0, 1.0, 1.0), 1e-12));
+        }
+    }
+
+    #[test]
+    fn decompose_translation_and_orientation() {
+        let translation = Point::new(-4.0, 0.5, 7.0);
+        for orientation in orientations() {
+            let m = Matrix::from_translation_and_orientation(translation, orientation);
+            let decomposition = m.decompose().unwrap();
+            assert_same_rotation(&decomposition.orientation, &orientation);
+            assert!(decomposition
+                .translation
+                .approximately(translation.as_vector(), 1e-12));
+        }
+    }
+
+    #[test]
+    fn decompose_round_trip_with_scale() {
+        let translation = Vector::new(1.0, -2.0, 3.0);
+        let scale = Vector::new(2.0, 0.5, 3.0);
+        for orientation in orientations() {
+            let m = Matrix::from_translation_orientation_and_scale(translation, orientation, scale);
+            let decomposition = m.decompose().unwrap();
+            assert!(decomposition.translation.approximately(translation, 1e-12));
+            assert!(decomposition.scale.approximately(scale, 1e-12));
+            assert_same_rotation(&decomposition.orientation, &orientation);
+            assert!(decomposition.to_matrix().approximately(m, 1e-12));
+        }
+    }
+
+    #[test]
+    fn decompose_reflection() {
+        let scale = Vector::new(1.0, -2.0, 1.0);
+        let m = Matrix::from_translation_orientation_and_scale(
+            Vector::zero(),
+            Rotor::identity(),
+            scale,
+        );
+        let decomposition = m.decompose().unwrap();
+        assert!(decomposition.scale.x < 0.0);
+        assert!(decomposition.to_matrix().approximately(m, 1e-12));
+    }
+
+    #[test]
+    fn decompose_column_translation() {
+        let translation = Vector::new(3.0, 2.0, 1.0);
+        let m = Matrix::from_translation(translation);
+        let decomposition = m.decompose_column_vector().unwrap();
+        assert!(decomposition.translation.approximately(translation, 1e-12));
+        assert_same_rotation(&decomposition.orientation, &Rotor::identity());
+
+        // The layout is never guessed, so a column translation is projective in the row layout
+        assert!(m.decompose().is_none());
+    }
+
+    #[test]
+    fn decompose_column_vector_without_translation() {
+        let scale = Vector::new(2.0, 1.0, 1.0);
+        for orientation in orientations() {
+            // A rotation alone has the same last row and column in both layouts
+            let m = Matrix::from_orientation(orientation).transposed();
+            let decomposition = m.decompose_column_vector().unwrap();
+            assert_same_rotation(&decomposition.orientation, &orientation);
+            assert!(decomposition
+                .translation
+                .approximately(Vector::zero(), 1e-12));
+
+            let m =
+                Matrix::from_translation_orientation_and_scale(Vector::zero(), orientation, scale)
+                    .transposed();
+            let decomposition = m.decompose_column_vector().unwrap();
+            assert_same_rotation(&decomposition.orientation, &orientation);
+            assert!(decomposition.scale.approximately(scale, 1e-12));
+            let v = Vector::new(0.2, -0.4, 0.9);
+            let expected = (v * decomposition.scale).rotated_by(&decomposition.orientation);
+            assert!((&m * v).approximately(expected, 1e-9));
+        }
+    }
+
+    #[test]
+    fn decompose_rejects_shear_and_projection() {
+        let sheared = Matrix::from([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.5, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert!(sheared.has_shear());
+        assert!(sheared.decompose().is_none());
+
+        let orientation = orientations()[20];
+        let scaled = Matrix::from_translation_orientation_and_scale(
+            Vector::zero(),
+            orientation,
+            Vector::new(1.0, 2.0, 3.0),
+        );
+        assert!(!scaled.has_shear());
+
+        assert!(Matrix::perspective(0.1, 100.0).decompose().is_none());
+        assert!(Matrix::zero().decompose().is_none());
+    }
+
+    #[test]
+    fn determinant() {
+        assert!(Matrix::identity().determinant().approximately(1.0, EPSILON));
+        assert!(general().determinant().approximately(-418.0, 1e-9));
+        assert!(general()
+            .transposed()
+            .determinant()
+            .approximately(-418.0, 1e-9));
+        assert!(trs().determinant().approximately(3.0, 1e-9));
+
+        let mut singular = general();
+        singular[3] = singular[0];
+        assert!(singular.determinant().approximately(0.0, 1e-9));
+    }
+
+    #[test]
+    fn determinant_of_product() {
+        let a = general();
+        let b = trs();
+        let product = (&a * &b).determinant();
+        assert!(product.approximately(a.determinant() * b.determinant(), 1e-9));
+    }
+
+    #[test]
+    fn inverse() {
+        for m in [general(), trs(), Matrix::perspective(0.1, 100.0)] {
+            let inverse = m.try_inverse().unwrap();
+            assert!((&m * &inverse).approximately(Matrix::identity(), 1e-9));
+            assert!((&inverse * &m).approximately(Matrix::identity(), 1e-9));
+        }
+    }
+
+    #[test]
+    fn singular_inverse() {
+        let mut singular = general();
+        singular[2] = [6.0, 4.0, -2.0, 8.0];
+        assert!(singular.try_inverse().is_none());
+        assert!(Matrix::zero().try_inverse().is_none());
+
+        // Tiny but well conditioned matrices are still invertible
+        let tiny = Matrix::from(1e-6);
+        assert!(tiny.try_inverse().is_some());
+    }
+
+    #[test]
+    fn affine_inverse_matches_general_inverse() {
+        let m = trs();
+        let affine = m.affine_inverse().unwrap();
+        assert!(affine.approximately(m.try_inverse().unwrap(), 1e-9));
+
+        // Translation in the last row, for row vectors
+        let transposed = m.transposed();
+        let affine = transposed.affine_inverse().unwrap();
+        assert!(affine.approximately(transposed.try_inverse().unwrap(), 1e-9));
+    }
+
+    #[test]
+    fn affine_inverse_rejects_projective() {
+        assert!(Matrix::perspective(0.1, 100.0).affine_inverse().is_none());
+
+        let mut flat = trs();
+        flat[2] = [0.0, 0.0, 0.0, 1.0];
+        assert!(flat.affine_inverse().is_none());
+    }
+
+    #[test]
+    fn affine_inverse_round_trips_points() {
+        let m = trs();
+        let inverse = m.affine_inverse().unwrap();
+        let point = Point::new(0.3, -4.0, 2.5);
+        let round_trip = &inverse * &(&m * &point);
+        assert!(round_trip.approximately(&point, 1e-9));
+    }
+
+    #[test]
+    fn translation_and_orientation_is_homogeneous() {
+        let plane = crate::bivec::Bivector::new(0.36, 0.48, 0.8);
+        let rotation = Rotor::from_angle_and_plane(0.7, plane);
+        let m = Matrix::from_translation_and_orientation(Point::new(1.0, 2.0, 3.0), rotation);
+        assert_eq!(m[3], [1.0, 2.0, 3.0, 1.0]);
+        assert!(m.determinant().approximately(1.0, 1e-12));
+    }
+
     #[test]
     fn test_look_at() {
         let eye = Point::origin();
@@ -586,6 +1387,19 @@ mod matrix_tests {
         assert!(view.approximately(expected, EPSILON));
     }
 
+    #[test]
+    fn look_at_moves_eye_to_origin() {
+        let eye = Point::new(3.0, -2.0, 4.0);
+        let target = Point::new(1.0, 5.0, -2.0);
+        let view = Matrix::look_at(eye, target, Vector::unit_y()).transposed();
+        assert!((&view * &eye).approximately(&Point::origin(), 1e-12));
+
+        // The target lies straight ahead, down -Z
+        let ahead = &view * &target;
+        let distance = (target - eye).length();
+        assert!(ahead.approximately(&Point::new(0.0, 0.0, -distance), 1e-12));
+    }
+
     #[test]
     fn perspective_aspect_ratio() {
         let fovy = std::f64::consts::FRAC_PI_2;
@@ -655,4 +1469,97 @@ mod matrix_tests {
         assert!(m.elements[3][1].approximately(Float::from(0.0), EPSILON));
         assert!(m.elements[3][3].approximately(Float::from(0.0), EPSILON));
     }
+
+    fn forward(clip: ClipSpace, distance: Float) -> Float {
+        match clip.handedness {
+            Handedness::Right => -distance,
+            Handedness::Left => distance,
+        }
+    }
+
+    #[test]
+    fn perspective_fov_depth_bounds() {
+        let (near, far) = (0.5, 50.0);
+        for (clip, near_depth) in [
+            (ClipSpace::VULKAN, 0.0),
+            (ClipSpace::OPENGL, -1.0),
+            (ClipSpace::DIRECT3D, 0.0),
+            (ClipSpace::WEBGPU, 0.0),
+        ] {
+            let m = Matrix::perspective_fov(1.2, 1.5, near, far, clip);
+            let n = &m * &Point::new(0.0, 0.0, forward(clip, near));
+            let f = &m * &Point::new(0.0, 0.0, forward(clip, far));
+            assert!(n.z.approximately(near_depth, 1e-9));
+            assert!(f.z.approximately(1.0, 1e-9));
+        }
+    }
+
+    #[test]
+    fn perspective_fov_edges() {
+        let (fovy, aspect, distance) = (precise::PI / 2.0, 2.0, 3.0);
+        for clip in [ClipSpace::VULKAN, ClipSpace::OPENGL, ClipSpace::DIRECT3D] {
+            let m = Matrix::perspective_fov(fovy, aspect, 0.1, 10.0, clip);
+            // At 90 degrees the top edge is as far up as the point is in front
+            let corner = Point::new(aspect * distance, distance, forward(clip, distance));
+            let projected = &m * &corner;
+            let y = if clip.y_flip { -1.0 } else { 1.0 };
+            assert!(projected.x.approximately(1.0, 1e-9));
+            assert!(projected.y.approximately(y, 1e-9));
+        }
+    }
+
+    #[test]
+    fn reversed_and_infinite_depth() {
+        let clip = ClipSpace::VULKAN.with_reversed_z(true);
+        let m = Matrix::perspective_fov(1.0, 1.0, 0.1, 100.0, clip);
+        assert!((&m * &Point::new(0.0, 0.0, -0.1))
+            .z
+            .approximately(1.0, 1e-9));
+        assert!((&m * &Point::new(0.0, 0.0, -100.0))
+            .z
+            .approximately(0.0, 1e-9));
+
+        let infinite = Matrix::perspective_infinite(1.0, 1.0, 0.1, clip);
+        assert!((&infinite * &Point::new(0.0, 0.0, -0.1))
+            .z
+            .approximately(1.0, 1e-9));
+        let distant = (&infinite * &Point::new(0.0, 0.0, -1e9)).z;
+        assert!(distant > 0.0 && distant < 1e-9);
+
+        let infinite = Matrix::perspective_infinite(1.0, 1.0, 0.1, ClipSpace::OPENGL);
+        let distant = (&infinite * &Point::new(0.0, 0.0, -1e9)).z;
+        assert!(distant < 1.0 && distant.approximately(1.0, 1e-9));
+    }
+
+    #[test]
+    fn off_center_frustum() {
+        let (l, r, b, t, n) = (-0.1, 0.3, -0.05, 0.2, 0.1);
+        let m = Matrix::frustum(l, r, b, t, n, 20.0, ClipSpace::WEBGPU);
+        let bottom_left = &m * &Point::new(l, b, -n);
+        let top_right = &m * &Point::new(r * 4.0, t * 4.0, -n * 4.0);
+        assert!(bottom_left.approximately(&Point::new(-1.0, -1.0, 0.0), 1e-9));
+        assert!(top_right.x.approximately(1.0, 1e-9));
+        assert!(top_right.y.approximately(1.0, 1e-9));
+
+        let flipped = Matrix::frustum(l, r, b, t, n, 20.0, ClipSpace::VULKAN);
+        let bottom_left = &flipped * &Point::new(l, b, -n);
+        assert!(bottom_left.approximately(&Point::new(-1.0, 1.0, 0.0), 1e-9));
+    }
+
+    #[test]
+    fn orthographic_off_center_bounds() {
+        let clip = ClipSpace::OPENGL;
+        let m = Matrix::orthographic_off_center(-1.0, 3.0, 0.0, 2.0, 1.0, 9.0, clip);
+        let min = &m * &Point::new(-1.0, 0.0, -1.0);
+        let max = &m * &Point::new(3.0, 2.0, -9.0);
+        assert!(min.approximately(&Point::new(-1.0, -1.0, -1.0), 1e-9));
+        assert!(max.approximately(&Point::new(1.0, 1.0, 1.0), 1e-9));
+
+        let clip = ClipSpace::DIRECT3D.with_reversed_z(true);
+        let m = Matrix::orthographic_off_center(-1.0, 3.0, 0.0, 2.0, 1.0, 9.0, clip);
+        let min = &m * &Point::new(-1.0, 0.0, 1.0);
+        let max = &m * &Point::new(3.0, 2.0, 9.0);
+        assert!(min.approximately(&Point::new(-1.0, -1.0, 1.0), 1e-9));
+        assert!(max.approximately(&Point::new(1.0, 1.0, 0.0), 1e-9));
+    }
 }
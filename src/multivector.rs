@@ -0,0 +1,586 @@
+//!
+//! Multivectors of the geometric algebra of 3D space
+//!
+//! A [Multivector] is a sum of a scalar, a [Vector], a [Bivector] and a trivector, the
+//! pseudoscalar `I = e1 e2 e3`. [Bivector] and [Rotor] are the grade two and even
+//! subalgebras of the same algebra and convert to and from a [Multivector] without loss,
+//! so reflections, projections and rotations can all be written with one set of products
+//!
+//! The basis is orthonormal and Euclidean, every basis vector squares to `+1`. Bivector
+//! components follow [Bivector], so `xz` is the coefficient of `e1 e3`
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::bivec::Bivector;
+use crate::rotor::Rotor;
+use crate::traits::FloatExt;
+use crate::Approximately;
+use crate::Float;
+use crate::Vector;
+use std::ops::Add;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+
+/// A general element of the geometric algebra of 3D space
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Multivector {
+    pub s: Float,
+    pub x: Float,
+    pub y: Float,
+    pub z: Float,
+    pub xy: Float,
+    pub xz: Float,
+    pub yz: Float,
+    pub xyz: Float,
+}
+
+/// The grade of each blade, indexed by the bitmask of its basis vectors
+const GRADES: [usize; 8] = [0, 1, 1, 2, 1, 2, 2, 3];
+
+/// The sign picked up when reordering the product of two basis blades, given as bitmasks,
+/// into canonical order
+///
+/// Shared with [crate::pga], whose blades are bitmasks over four basis vectors
+pub(crate) fn reorder_sign(a: usize, b: usize) -> Float {
+    let mut a = a >> 1;
+    let mut swaps = 0;
+    while a != 0 {
+        swaps += (a & b).count_ones();
+        a >>= 1;
+    }
+
+    if swaps % 2 == 0 {
+        Float::ONE
+    } else {
+        -Float::ONE
+    }
+}
+
+impl Multivector {
+    /// Constructs a new [Multivector] from each of its components
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn new<F: Into<Float>>(s: F, x: F, y: F, z: F, xy: F, xz: F, yz: F, xyz: F) -> Self {
+        Self {
+            s: s.into(),
+            x: x.into(),
+            y: y.into(),
+            z: z.into(),
+            xy: xy.into(),
+            xz: xz.into(),
+            yz: yz.into(),
+            xyz: xyz.into(),
+        }
+    }
+
+    #[inline]
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a [Multivector] with only a scalar part
+    #[inline]
+    pub fn scalar<F: Into<Float>>(s: F) -> Self {
+        Self {
+            s: s.into(),
+            ..Self::default()
+        }
+    }
+
+    /// The unit pseudoscalar `I = e1 e2 e3`, the oriented unit volume
+    #[inline]
+    pub fn pseudoscalar() -> Self {
+        Self {
+            xyz: Float::ONE,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the grade one part of this [Multivector]
+    #[inline]
+    pub fn vector(&self) -> Vector {
+        Vector::new(self.x, self.y, self.z)
+    }
+
+    /// Returns the grade two part of this [Multivector]
+    #[inline]
+    pub fn bivector(&self) -> Bivector {
+        Bivector::new(self.xy, self.xz, self.yz)
+    }
+
+    /// Returns the even part of this [Multivector] as a [Rotor], without normalizing it
+    #[inline]
+    pub fn rotor(&self) -> Rotor {
+        Rotor::new(self.bivector(), self.s)
+    }
+
+    /// Returns the part of this [Multivector] of the given `grade`, which is zero for
+    /// grades above three
+    pub fn grade(&self, grade: usize) -> Self {
+        self.map(|blade, value| {
+            if GRADES[blade] == grade {
+                value
+            } else {
+                Float::ZERO
+            }
+        })
+    }
+
+    /// Computes the geometric product of two [Multivector]'s
+    pub fn geometric(&self, other: &Self) -> Self {
+        self.product(other, |_, _| true)
+    }
+
+    /// Computes the outer (wedge) product of two [Multivector]'s
+    ///
+    /// For two vectors this is the [Bivector] of [Vector::wedge]
+    pub fn outer(&self, other: &Self) -> Self {
+        self.product(other, |a, b| a & b == 0)
+    }
+
+    /// Computes the inner product of two [Multivector]'s, taken as the left contraction
+    ///
+    /// For two vectors this is the dot product. Contracting a vector onto a blade gives the part
+    /// of the blade orthogonal to the vector, one grade lower, and a higher grade contracted
+    /// onto a lower one is zero
+    pub fn inner(&self, other: &Self) -> Self {
+        self.product(other, |a, b| a & !b == 0)
+    }
+
+    /// Returns the reverse of this [Multivector], which negates the bivector and trivector parts
+    ///
+    /// Reversing a [Rotor] gives the inverse rotation, see [Rotor::reversed]
+    pub fn reversed(&self) -> Self {
+        self.map(|blade, value| match GRADES[blade] {
+            2 | 3 => -value,
+            _ => value,
+        })
+    }
+
+    /// Returns the grade involution of this [Multivector], which negates the odd grades
+    pub fn involuted(&self) -> Self {
+        self.map(|blade, value| match GRADES[blade] {
+            1 | 3 => -value,
+            _ => value,
+        })
+    }
+
+    /// Returns the Clifford conjugate of this [Multivector], the reverse of its grade involution
+    pub fn conjugated(&self) -> Self {
+        self.map(|blade, value| match GRADES[blade] {
+            1 | 2 => -value,
+            _ => value,
+        })
+    }
+
+    /// Returns the dual of this [Multivector], its product with the pseudoscalar `I A`
+    ///
+    /// The dual of a vector is the [Bivector] of the plane orthogonal to it, matching
+    /// [Bivector::from_axis_vector]
+    pub fn dual(&self) -> Self {
+        Self::pseudoscalar().geometric(self)
+    }
+
+    /// Returns the inverse of [Multivector::dual], the product `-I A`
+    pub fn undual(&self) -> Self {
+        -self.dual()
+    }
+
+    /// Computes the squared magnitude of this [Multivector], the sum of the squares of
+    /// its components
+    #[inline]
+    pub fn magnitude_sq(&self) -> Float {
+        self.geometric(&self.reversed()).s
+    }
+
+    /// Computes the magnitude of this [Multivector]
+    #[inline]
+    pub fn magnitude(&self) -> Float {
+        self.magnitude_sq().sqrt()
+    }
+
+    /// Returns the inverse of this [Multivector] under the geometric product
+    ///
+    /// Only blades and versors, products of vectors, are supported. Returns `None` if this
+    /// [Multivector] is zero or not a versor
+    pub fn inverse(&self) -> Option<Self> {
+        let norm = self.geometric(&self.reversed());
+        let magnitude_sq = norm.s;
+        if magnitude_sq.abs() <= Float::EPSILON {
+            return None;
+        }
+
+        // A versor times its reverse is a scalar, anything left over means there is no
+        // inverse of this form
+        let rest = (norm - Self::scalar(magnitude_sq)).magnitude_sq();
+        if rest > magnitude_sq * magnitude_sq * Float::from(1e-12) {
+            return None;
+        }
+
+        Some(self.reversed() * (Float::ONE / magnitude_sq))
+    }
+
+    /// Rotates this [Multivector] by a [Rotor], the sandwich product `R A R̃`
+    pub fn rotated_by(&self, rotor: &Rotor) -> Self {
+        let r = Self::from(*rotor);
+        r.geometric(self).geometric(&r.reversed())
+    }
+
+    /// Reflects this [Multivector] in the plane through the origin orthogonal to `normal`
+    ///
+    /// Every grade is reflected, so vectors are mirrored while the pseudoscalar changes sign
+    pub fn reflected(&self, normal: &Vector) -> Self {
+        let n = Self::from(normal.normalized());
+        n.geometric(&self.involuted()).geometric(&n)
+    }
+
+    /// Projects this [Multivector] onto the subspace of `blade`, `(A ⌋ B) B⁻¹`
+    ///
+    /// Returns `None` if `blade` has no inverse
+    pub fn projected(&self, blade: &Self) -> Option<Self> {
+        blade
+            .inverse()
+            .map(|inverse| self.inner(blade).geometric(&inverse))
+    }
+
+    /// The components of this [Multivector], indexed by the bitmask of their basis vectors
+    #[inline]
+    fn blades(&self) -> [Float; 8] {
+        [
+            self.s, self.x, self.y, self.xy, self.z, self.xz, self.yz, self.xyz,
+        ]
+    }
+
+    #[inline]
+    fn from_blades(b: [Float; 8]) -> Self {
+        Self {
+            s: b[0],
+            x: b[1],
+            y: b[2],
+            xy: b[3],
+            z: b[4],
+            xz: b[5],
+            yz: b[6],
+            xyz: b[7],
+        }
+    }
+
+    /// Applies `f(blade, value)` to each component of this [Multivector]
+    #[inline]
+    fn map<F: Fn(usize, Float) -> Float>(&self, f: F) -> Self {
+        let mut blades = self.blades();
+        for (blade, value) in blades.iter_mut().enumerate() {
+            *value = f(blade, *value);
+        }
+        Self::from_blades(blades)
+    }
+
+    /// The geometric product restricted to the pairs of basis blades accepted by `keep`
+    ///
+    /// The outer and inner products are the parts of the geometric product of each pair of
+    /// blades with the grade they select, which `keep` decides from the blade bitmasks
+    fn product<F: Fn(usize, usize) -> bool>(&self, other: &Self, keep: F) -> Self {
+        let a = self.blades();
+        let b = other.blades();
+        let mut result = [Float::ZERO; 8];
+        for i in 0..8 {
+            if a[i] == Float::ZERO {
+                continue;
+            }
+            for j in 0..8 {
+                if keep(i, j) {
+                    result[i ^ j] += reorder_sign(i, j) * a[i] * b[j];
+                }
+            }
+        }
+        Self::from_blades(result)
+    }
+}
+
+impl From<Float> for Multivector {
+    fn from(s: Float) -> Self {
+        Self::scalar(s)
+    }
+}
+
+impl From<Vector> for Multivector {
+    fn from(v: Vector) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<Bivector> for Multivector {
+    fn from(b: Bivector) -> Self {
+        Self {
+            xy: b.xy,
+            xz: b.xz,
+            yz: b.yz,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<Rotor> for Multivector {
+    fn from(r: Rotor) -> Self {
+        Self {
+            s: r.s,
+            xy: r.b.xy,
+            xz: r.b.xz,
+            yz: r.b.yz,
+            ..Self::default()
+        }
+    }
+}
+
+macro_rules! multivector_componentwise_binop {
+    ($lhs:ty, $rhs:ty, $func:ident, $trait:ident) => {
+        impl $trait<$rhs> for $lhs {
+            type Output = Multivector;
+            fn $func(self, other: $rhs) -> Self::Output {
+                let a = self.blades();
+                let b = other.blades();
+                Multivector::from_blades(std::array::from_fn(|i| Float::$func(a[i], b[i])))
+            }
+        }
+    };
+}
+
+multivector_componentwise_binop!(Multivector, Multivector, add, Add);
+multivector_componentwise_binop!(&Multivector, Multivector, add, Add);
+multivector_componentwise_binop!(Multivector, &Multivector, add, Add);
+multivector_componentwise_binop!(&Multivector, &Multivector, add, Add);
+
+multivector_componentwise_binop!(Multivector, Multivector, sub, Sub);
+multivector_componentwise_binop!(&Multivector, Multivector, sub, Sub);
+multivector_componentwise_binop!(Multivector, &Multivector, sub, Sub);
+multivector_componentwise_binop!(&Multivector, &Multivector, sub, Sub);
+
+macro_rules! multivector_geometric_mul {
+    ($lhs:ty, $rhs:ty) => {
+        impl Mul<$rhs> for $lhs {
+            type Output = Multivector;
+            fn mul(self, other: $rhs) -> Self::Output {
+                self.geometric(&other)
+            }
+        }
+    };
+}
+
+multivector_geometric_mul!(Multivector, Multivector);
+multivector_geometric_mul!(&Multivector, Multivector);
+multivector_geometric_mul!(Multivector, &Multivector);
+multivector_geometric_mul!(&Multivector, &Multivector);
+
+impl Mul<Float> for Multivector {
+    type Output = Multivector;
+
+    fn mul(self, other: Float) -> Self::Output {
+        self.map(|_, value| value * other)
+    }
+}
+
+impl Mul<Multivector> for Float {
+    type Output = Multivector;
+
+    fn mul(self, other: Multivector) -> Self::Output {
+        other * self
+    }
+}
+
+impl Neg for Multivector {
+    type Output = Multivector;
+
+    fn neg(self) -> Self::Output {
+        self.map(|_, value| -value)
+    }
+}
+
+impl std::fmt::Display for Multivector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{:+.3}, ({:+.3}, {:+.3}, {:+.3}), ({:+.3}, {:+.3}, {:+.3}), {:+.3}]",
+            self.s, self.x, self.y, self.z, self.xy, self.xz, self.yz, self.xyz
+        )
+    }
+}
+
+impl Approximately for Multivector {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        let a = self.blades();
+        let b = other.blades();
+        (0..8).all(|i| a[i].approximately(b[i], epsilon))
+    }
+}
+
+#[cfg(test)]
+mod multivector_tests {
+    use super::*;
+    use crate::constant::PI;
+
+    const EPSILON: Float = 1e-12;
+
+    fn e1() -> Multivector {
+        Multivector::from(Vector::unit_x())
+    }
+
+    fn e2() -> Multivector {
+        Multivector::from(Vector::unit_y())
+    }
+
+    fn e3() -> Multivector {
+        Multivector::from(Vector::unit_z())
+    }
+
+    #[test]
+    fn basis_products() {
+        let e12 = Multivector::from(Bivector::unit_xy());
+        assert_eq!(e1() * e1(), Multivector::scalar(1.0));
+        assert_eq!(e1() * e2(), e12);
+        assert_eq!(e2() * e1(), -e12);
+        assert_eq!(e12 * e12, Multivector::scalar(-1.0));
+        assert_eq!(e1() * e2() * e3(), Multivector::pseudoscalar());
+
+        let i = Multivector::pseudoscalar();
+        assert_eq!(i * i, Multivector::scalar(-1.0));
+        assert_eq!(i * e1(), e1() * i);
+    }
+
+    #[test]
+    fn vector_products() {
+        let a = Vector::new(1.0, 2.0, -1.0);
+        let b = Vector::new(0.5, -3.0, 2.0);
+        let (ma, mb) = (Multivector::from(a), Multivector::from(b));
+
+        let geometric = ma * mb;
+        assert!(geometric.s.approximately(a.dot(&b), EPSILON));
+        assert!(geometric.bivector().approximately(a.wedge(b), EPSILON));
+        assert!(ma
+            .outer(&mb)
+            .approximately(Multivector::from(a.wedge(b)), EPSILON));
+        assert!(ma
+            .inner(&mb)
+            .approximately(Multivector::scalar(a.dot(&b)), EPSILON));
+    }
+
+    #[test]
+    fn contraction_lowers_grade() {
+        let e12 = Multivector::from(Bivector::unit_xy());
+        assert_eq!(e1().inner(&e12), e2());
+        assert_eq!(e2().inner(&e12), -e1());
+        assert_eq!(e3().inner(&e12), Multivector::zero());
+        assert_eq!(e12.inner(&e1()), Multivector::zero());
+        assert_eq!(
+            e12.inner(&Multivector::pseudoscalar()),
+            Multivector::from(Vector::unit_z()) * -1.0
+        );
+    }
+
+    #[test]
+    fn involutions() {
+        let a = Multivector::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0);
+        assert_eq!(
+            a.reversed(),
+            Multivector::new(1.0, 2.0, 3.0, 4.0, -5.0, -6.0, -7.0, -8.0)
+        );
+        assert_eq!(
+            a.involuted(),
+            Multivector::new(1.0, -2.0, -3.0, -4.0, 5.0, 6.0, 7.0, -8.0)
+        );
+        assert_eq!(
+            a.conjugated(),
+            Multivector::new(1.0, -2.0, -3.0, -4.0, -5.0, -6.0, -7.0, 8.0)
+        );
+
+        // The reverse of a product is the product of the reverses in the opposite order
+        let b = Multivector::new(-1.0, 0.5, 2.0, 0.0, 1.0, -3.0, 0.25, 2.0);
+        assert!((a * b)
+            .reversed()
+            .approximately(b.reversed() * a.reversed(), EPSILON));
+    }
+
+    #[test]
+    fn grade_projection() {
+        let a = Multivector::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0);
+        let sum = (0..4).fold(Multivector::zero(), |sum, k| sum + a.grade(k));
+        assert_eq!(sum, a);
+        assert_eq!(a.grade(1), Multivector::from(Vector::new(2.0, 3.0, 4.0)));
+        assert_eq!(a.grade(4), Multivector::zero());
+    }
+
+    #[test]
+    fn dual() {
+        let axis = Vector::new(1.0, -2.0, 0.5);
+        let dual = Multivector::from(axis).dual();
+        assert!(dual.approximately(Multivector::from(Bivector::from_axis_vector(axis)), EPSILON));
+        assert!(dual
+            .undual()
+            .approximately(Multivector::from(axis), EPSILON));
+        assert_eq!(Multivector::scalar(1.0).dual(), Multivector::pseudoscalar());
+    }
+
+    #[test]
+    fn rotor_conversions_are_lossless() {
+        let rotor = Rotor::from_axis_angle(Vector::new(1.0, 2.0, 3.0), 0.8);
+        let other = Rotor::from_axis_angle(Vector::new(-1.0, 0.0, 1.0), 2.1);
+        let multivector = Multivector::from(rotor);
+        assert_eq!(multivector.rotor(), rotor);
+
+        // The rotor product is the geometric product
+        let product = (multivector * Multivector::from(other)).rotor();
+        assert!(product.approximately(rotor * other, EPSILON));
+    }
+
+    #[test]
+    fn rotation_matches_rotor() {
+        let rotor = Rotor::from_axis_angle(Vector::new(1.0, 2.0, 3.0), 0.8);
+        let v = Vector::new(0.3, -1.0, 2.0);
+        let rotated = Multivector::from(v).rotated_by(&rotor);
+        assert!(rotated.approximately(Multivector::from(v.rotated_by(&rotor)), EPSILON));
+
+        // Bivectors rotate along with their axis vectors
+        let plane = Multivector::from(Bivector::from_axis_vector(v)).rotated_by(&rotor);
+        let expected = Bivector::from_axis_vector(v.rotated_by(&rotor));
+        assert!(plane.approximately(Multivector::from(expected), EPSILON));
+    }
+
+    #[test]
+    fn reflection() {
+        let v = Multivector::from(Vector::new(1.0, 2.0, 3.0));
+        let reflected = v.reflected(&Vector::new(0.0, 2.0, 0.0));
+        assert!(reflected.approximately(Multivector::from(Vector::new(1.0, -2.0, 3.0)), EPSILON));
+
+        // Two reflections make a rotation by twice the angle between the planes
+        let a = Vector::unit_x();
+        let b = Vector::new((PI / 8.0).cos(), (PI / 8.0).sin(), 0.0);
+        let twice = e1().reflected(&a).reflected(&b);
+        assert!(twice.approximately(e2() * (PI / 4.0).sin() + e1() * (PI / 4.0).cos(), EPSILON));
+
+        let i = Multivector::pseudoscalar();
+        assert_eq!(i.reflected(&Vector::unit_z()), -i);
+    }
+
+    #[test]
+    fn inverse_and_projection() {
+        let v = Multivector::from(Vector::new(0.0, 3.0, 4.0));
+        let inverse = v.inverse().unwrap();
+        assert!((v * inverse).approximately(Multivector::scalar(1.0), EPSILON));
+        assert!(Multivector::zero().inverse().is_none());
+        assert!((Multivector::scalar(1.0) + e1()).inverse().is_none());
+
+        let plane = e1().outer(&e2()) * 2.0;
+        let projected = Multivector::from(Vector::new(1.0, 2.0, 3.0))
+            .projected(&plane)
+            .unwrap();
+        assert!(projected.approximately(Multivector::from(Vector::new(1.0, 2.0, 0.0)), EPSILON));
+    }
+}
@@ -0,0 +1,935 @@
+//!
+//! Projective geometric algebra of 3D space
+//!
+//! In the projective geometric algebra (PGA) a fourth basis vector `e0` squares to zero, which
+//! lets planes, lines and points all be elements of one algebra: planes are vectors, lines are
+//! bivectors and points are trivectors. Intersecting them is the outer product [Meet], and
+//! spanning them is the regressive product [Join]
+//!
+//! Rigid motions are [Motor]s, the even elements that combine a [Rotor] with a translation.
+//! Motors compose by multiplication, interpolate along screw motions and act on every kind
+//! of element with the same sandwich product, without any matrices
+//!
+//! Every element converts to and from the crate's [Point], [Line] and [Plane] types
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::bivec::Bivector;
+use crate::line::Line;
+use crate::multivector::reorder_sign;
+use crate::plane::Plane;
+use crate::rotor::Rotor;
+use crate::traits::FloatExt;
+use crate::traits::Zero;
+use crate::Approximately;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+use std::ops::Mul;
+
+/// Tolerance below which lengths and weights are treated as zero
+const TOLERANCE: f64 = 1e-12;
+
+/// Span two elements with the regressive product, giving the smallest element containing both
+pub trait Join<Rhs = Self> {
+    /// The element spanned by both items
+    type Output;
+
+    fn join(&self, other: &Rhs) -> Self::Output;
+}
+
+/// Intersect two elements with the outer product, giving the largest element within both
+pub trait Meet<Rhs = Self> {
+    /// The element common to both items
+    type Output;
+
+    fn meet(&self, other: &Rhs) -> Self::Output;
+}
+
+/// A plane `a x + b y + c z + d = 0`, the grade one elements of the algebra
+///
+/// The normal `(a, b, c)` need not be of unit length. When it is zero this is the ideal
+/// plane at infinity
+#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq)]
+pub struct PgaPlane {
+    pub a: Float,
+    pub b: Float,
+    pub c: Float,
+    pub d: Float,
+}
+
+/// A line in Plücker coordinates, the grade two elements of the algebra
+///
+/// A line through the point `p` along `direction` has a `moment` of `p × direction`. When the
+/// direction is zero this is an ideal line at infinity. General bivectors, whose direction and
+/// moment are not orthogonal, describe screw axes rather than lines
+#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq)]
+pub struct PgaLine {
+    pub direction: Vector,
+    pub moment: Vector,
+}
+
+/// A homogeneous point `(x, y, z, w)`, the grade three elements of the algebra
+///
+/// The Euclidean point is `(x / w, y / w, z / w)`. When `w` is zero this is an ideal point,
+/// the direction `(x, y, z)` at infinity
+#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq)]
+pub struct PgaPoint {
+    pub x: Float,
+    pub y: Float,
+    pub z: Float,
+    pub w: Float,
+}
+
+/// A rigid motion, the combination of a rotation and a translation into one screw motion
+///
+/// As with [Rotor], the product `a * b` is the motion that applies `b` first and then `a`
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Motor {
+    /// The scalar part
+    s: Float,
+    /// The Euclidean bivector part, the same as that of a [Rotor]
+    b: Bivector,
+    /// The ideal bivector part, the coefficients of `e01`, `e02` and `e03`
+    t: Vector,
+    /// The pseudoscalar part, the coefficient of `e0123`
+    p: Float,
+}
+
+impl PgaPlane {
+    #[inline]
+    pub fn new<F: Into<Float>>(a: F, b: F, c: F, d: F) -> Self {
+        Self {
+            a: a.into(),
+            b: b.into(),
+            c: c.into(),
+            d: d.into(),
+        }
+    }
+
+    /// The normal [Vector] `(a, b, c)` of this plane
+    #[inline]
+    pub fn normal(&self) -> Vector {
+        Vector::new(self.a, self.b, self.c)
+    }
+
+    /// Returns this plane scaled to have a unit normal
+    pub fn normalized(&self) -> Self {
+        let length = self.normal().length();
+        Self::new(
+            self.a / length,
+            self.b / length,
+            self.c / length,
+            self.d / length,
+        )
+    }
+
+    /// Returns the signed distance from `point` to this plane, positive on the side the
+    /// normal points to
+    pub fn signed_distance(&self, point: &Point) -> Float {
+        let plane = self.normalized();
+        plane.normal().dot(&point.as_vector()) + plane.d
+    }
+
+    /// Converts this plane to a [Plane], or `None` if this is the plane at infinity
+    pub fn to_plane(&self) -> Option<Plane> {
+        let length = self.normal().length();
+        (length > Float::from(TOLERANCE))
+            .then(|| Plane::new(self.normal() / length, -self.d / length))
+    }
+
+    /// Constructs the plane through three points, oriented with its normal by the right hand
+    /// rule about `a`, `b` and `c`
+    pub fn from_points(a: &PgaPoint, b: &PgaPoint, c: &PgaPoint) -> Self {
+        a.join(b).join(c)
+    }
+}
+
+impl PgaLine {
+    #[inline]
+    pub fn new(direction: Vector, moment: Vector) -> Self {
+        Self { direction, moment }
+    }
+
+    /// Constructs the line through `point` along `direction`
+    #[inline]
+    pub fn from_point_and_direction(point: Point, direction: Vector) -> Self {
+        Self::new(direction, point.as_vector().cross(&direction))
+    }
+
+    /// Returns this line scaled to have a unit direction
+    pub fn normalized(&self) -> Self {
+        let length = self.direction.length();
+        Self::new(self.direction / length, self.moment / length)
+    }
+
+    /// Returns the point on this line closest to the origin, or `None` for a line at infinity
+    pub fn closest_point_to_origin(&self) -> Option<Point> {
+        let length_sq = self.direction.length_sq();
+        (length_sq > Float::from(TOLERANCE * TOLERANCE)).then(|| {
+            let p = self.direction.cross(&self.moment) / length_sq;
+            Point::new(p.x, p.y, p.z)
+        })
+    }
+
+    /// Converts this line to a [Line], or `None` if this is a line at infinity
+    pub fn to_line(&self) -> Option<Line> {
+        self.closest_point_to_origin()
+            .map(|origin| Line::new(origin, self.direction))
+    }
+}
+
+impl PgaPoint {
+    #[inline]
+    pub fn new<F: Into<Float>>(x: F, y: F, z: F, w: F) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+            z: z.into(),
+            w: w.into(),
+        }
+    }
+
+    /// Constructs the ideal point at infinity in the given `direction`
+    #[inline]
+    pub fn ideal(direction: Vector) -> Self {
+        Self::new(direction.x, direction.y, direction.z, Float::ZERO)
+    }
+
+    /// Test whether this is an ideal point, a direction at infinity
+    #[inline]
+    pub fn is_ideal(&self) -> bool {
+        self.w.abs() <= Float::from(TOLERANCE)
+    }
+
+    /// Converts this point to a [Point], or `None` if this is an ideal point
+    pub fn to_point(&self) -> Option<Point> {
+        (!self.is_ideal()).then(|| Point::new(self.x / self.w, self.y / self.w, self.z / self.w))
+    }
+
+    /// Constructs the point where three planes meet
+    pub fn from_planes(a: &PgaPlane, b: &PgaPlane, c: &PgaPlane) -> Self {
+        a.meet(b).meet(c)
+    }
+}
+
+impl Motor {
+    pub fn identity() -> Self {
+        Self::from_rotor(Rotor::identity())
+    }
+
+    /// Constructs a [Motor] that rotates about the origin by `rotor`
+    pub fn from_rotor(rotor: Rotor) -> Self {
+        Self {
+            s: rotor.s,
+            b: rotor.b,
+            t: Vector::zero(),
+            p: Float::ZERO,
+        }
+    }
+
+    /// Constructs a [Motor] that translates by `translation`
+    pub fn from_translation(translation: Vector) -> Self {
+        Self {
+            s: Float::ONE,
+            b: Bivector::zero(),
+            t: translation / -Float::from(2.0),
+            p: Float::ZERO,
+        }
+    }
+
+    /// Constructs a [Motor] that rotates about the origin by `rotor` and then translates
+    /// by `translation`
+    pub fn from_rotor_and_translation(rotor: Rotor, translation: Vector) -> Self {
+        Self::from_translation(translation) * Self::from_rotor(rotor)
+    }
+
+    /// Constructs the screw motion that rotates right-handedly by `angle` radians about
+    /// `axis` while translating `distance` along it
+    pub fn from_screw<F: Into<Float>>(axis: &PgaLine, angle: F, distance: F) -> Self {
+        let axis = PgaMultivector::from(axis.normalized());
+        let rotation = axis.scaled(-angle.into() / Float::from(2.0));
+        let translation = pseudoscalar()
+            .geometric(&axis)
+            .scaled(distance.into() / Float::from(2.0));
+        Self::from(exp(&rotation.add(&translation)))
+    }
+
+    /// Returns the reverse of this [Motor], which is the inverse motion when normalized
+    pub fn reversed(&self) -> Self {
+        Self {
+            s: self.s,
+            b: -self.b,
+            t: -self.t,
+            p: self.p,
+        }
+    }
+
+    /// Returns this [Motor] scaled to be a rigid motion, with `M M̃ = 1`
+    pub fn normalized(&self) -> Self {
+        let m = PgaMultivector::from(*self);
+        let norm = m.geometric(&m.reversed());
+        let (s, p) = (norm.0[0], norm.0[15]);
+
+        // The inverse square root of s + p I, as I squares to zero
+        let root = s.sqrt();
+        let mut inverse = PgaMultivector::scalar(Float::ONE / root);
+        inverse.0[15] = -p / (Float::from(2.0) * s * root);
+        Self::from(m.geometric(&inverse))
+    }
+
+    /// Returns the rotation of this [Motor] about the origin
+    pub fn rotor(&self) -> Rotor {
+        Rotor::new(self.b, self.s).normalized()
+    }
+
+    /// Returns the translation of this [Motor], where it moves the origin to
+    pub fn translation(&self) -> Vector {
+        self.transform(&Point::origin()).as_vector()
+    }
+
+    /// Applies this [Motor] to a [Point]
+    pub fn transform(&self, point: &Point) -> Point {
+        self.transform_point(&PgaPoint::from(*point))
+            .to_point()
+            .expect(POINT_EXPECTED_MESSAGE)
+    }
+
+    /// Applies the rotation of this [Motor] to a direction [Vector], ignoring its translation
+    pub fn transform_vector(&self, vector: &Vector) -> Vector {
+        let p = self.transform_point(&PgaPoint::ideal(*vector));
+        Vector::new(p.x, p.y, p.z)
+    }
+
+    /// Applies this [Motor] to a [PgaPoint]
+    pub fn transform_point(&self, point: &PgaPoint) -> PgaPoint {
+        PgaPoint::from(self.sandwich(&PgaMultivector::from(*point)))
+    }
+
+    /// Applies this [Motor] to a [PgaLine]
+    pub fn transform_line(&self, line: &PgaLine) -> PgaLine {
+        PgaLine::from(self.sandwich(&PgaMultivector::from(*line)))
+    }
+
+    /// Applies this [Motor] to a [PgaPlane]
+    pub fn transform_plane(&self, plane: &PgaPlane) -> PgaPlane {
+        PgaPlane::from(self.sandwich(&PgaMultivector::from(*plane)))
+    }
+
+    /// Interpolates along the screw motion from this [Motor] to `to`, by the amount of `weight`
+    ///
+    /// Both the rotation and the translation progress at a constant rate, and the shortest
+    /// rotation is taken
+    pub fn interpolate<F: Into<Float>>(&self, to: &Self, weight: F) -> Self {
+        let relative = PgaMultivector::from(to.normalized() * self.normalized().reversed());
+        let relative = if relative.0[0] < Float::ZERO {
+            relative.scaled(-Float::ONE)
+        } else {
+            relative
+        };
+
+        let step = exp(&log(&relative).scaled(weight.into()));
+        Self::from(step) * *self
+    }
+
+    /// Blends several [Motor]s by a weighted sum, the linear blending of dual quaternions
+    ///
+    /// Each motor is first flipped onto the same half of the manifold as the first, so the
+    /// blend takes the shortest rotation. The sum is normalized back into a rigid motion,
+    /// which avoids the volume loss of blending matrices. Returns `None` if `weighted` is
+    /// empty or the weights cancel out
+    pub fn blend(weighted: &[(Motor, Float)]) -> Option<Self> {
+        let (first, _) = weighted.first()?;
+        let sum = weighted
+            .iter()
+            .fold(PgaMultivector::default(), |sum, (motor, weight)| {
+                let same_half = motor.s * first.s + motor.b.dot(&first.b) >= Float::ZERO;
+                let weight = if same_half { *weight } else { -*weight };
+                sum.add(&PgaMultivector::from(*motor).scaled(weight))
+            });
+
+        let motor = Self::from(sum);
+        let real = motor.s * motor.s + motor.b.magnitude_sq();
+        (real > Float::from(TOLERANCE)).then(|| motor.normalized())
+    }
+
+    /// The sandwich product `M X M̃`
+    fn sandwich(&self, x: &PgaMultivector) -> PgaMultivector {
+        let m = PgaMultivector::from(*self);
+        m.geometric(x).geometric(&m.reversed())
+    }
+}
+
+static POINT_EXPECTED_MESSAGE: &str = "rigid motions keep finite points finite";
+
+impl Default for Motor {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul for Motor {
+    type Output = Motor;
+
+    fn mul(self, rhs: Motor) -> Self::Output {
+        Self::from(PgaMultivector::from(self).geometric(&PgaMultivector::from(rhs)))
+    }
+}
+
+impl From<Rotor> for Motor {
+    fn from(rotor: Rotor) -> Self {
+        Self::from_rotor(rotor)
+    }
+}
+
+impl Join for PgaPoint {
+    type Output = PgaLine;
+
+    /// The line through both points, directed from this point to `other`
+    fn join(&self, other: &PgaPoint) -> Self::Output {
+        PgaLine::from(PgaMultivector::from(*self).regressive(&PgaMultivector::from(*other)))
+    }
+}
+
+impl Join<PgaPoint> for PgaLine {
+    type Output = PgaPlane;
+
+    /// The plane containing this line and `other`
+    fn join(&self, other: &PgaPoint) -> Self::Output {
+        PgaPlane::from(PgaMultivector::from(*self).regressive(&PgaMultivector::from(*other)))
+    }
+}
+
+impl Join<PgaLine> for PgaPoint {
+    type Output = PgaPlane;
+
+    /// The plane containing this point and `other`
+    fn join(&self, other: &PgaLine) -> Self::Output {
+        PgaPlane::from(PgaMultivector::from(*self).regressive(&PgaMultivector::from(*other)))
+    }
+}
+
+impl Meet for PgaPlane {
+    type Output = PgaLine;
+
+    /// The line where both planes cross, directed along the cross product of their normals
+    fn meet(&self, other: &PgaPlane) -> Self::Output {
+        PgaLine::from(PgaMultivector::from(*self).outer(&PgaMultivector::from(*other)))
+    }
+}
+
+impl Meet<PgaLine> for PgaPlane {
+    type Output = PgaPoint;
+
+    /// The point where `other` crosses this plane, ideal when they are parallel
+    fn meet(&self, other: &PgaLine) -> Self::Output {
+        PgaPoint::from(PgaMultivector::from(*self).outer(&PgaMultivector::from(*other)))
+    }
+}
+
+impl Meet<PgaPlane> for PgaLine {
+    type Output = PgaPoint;
+
+    /// The point where this line crosses `other`, ideal when they are parallel
+    fn meet(&self, other: &PgaPlane) -> Self::Output {
+        PgaPoint::from(PgaMultivector::from(*self).outer(&PgaMultivector::from(*other)))
+    }
+}
+
+impl From<Point> for PgaPoint {
+    fn from(point: Point) -> Self {
+        Self::new(point.x, point.y, point.z, Float::ONE)
+    }
+}
+
+impl From<Plane> for PgaPlane {
+    fn from(plane: Plane) -> Self {
+        Self::new(plane.norm.x, plane.norm.y, plane.norm.z, -plane.dist)
+    }
+}
+
+impl From<Line> for PgaLine {
+    fn from(line: Line) -> Self {
+        Self::from_point_and_direction(line.origin, line.direction)
+    }
+}
+
+impl Approximately for PgaPoint {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        self.x.approximately(other.x, epsilon)
+            && self.y.approximately(other.y, epsilon)
+            && self.z.approximately(other.z, epsilon)
+            && self.w.approximately(other.w, epsilon)
+    }
+}
+
+impl Approximately for PgaLine {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        self.direction.approximately(other.direction, epsilon)
+            && self.moment.approximately(other.moment, epsilon)
+    }
+}
+
+impl Approximately for PgaPlane {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        self.a.approximately(other.a, epsilon)
+            && self.b.approximately(other.b, epsilon)
+            && self.c.approximately(other.c, epsilon)
+            && self.d.approximately(other.d, epsilon)
+    }
+}
+
+impl Approximately for Motor {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        self.s.approximately(other.s, epsilon)
+            && self.b.approximately(other.b, epsilon)
+            && self.t.approximately(other.t, epsilon)
+            && self.p.approximately(other.p, epsilon)
+    }
+}
+
+/// A general element of the projective algebra, the components indexed by the bitmask of
+/// their basis vectors with `e0` as the lowest bit. Unlike [crate::multivector::Multivector]
+/// it includes the degenerate basis vector `e0`
+///
+/// The public types are all views of this, with the blade orientations chosen so that a
+/// point is `x e032 + y e013 + z e021 + w e123`
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+struct PgaMultivector([Float; 16]);
+
+// Indices of the blades used by the public types
+const E0: usize = 0b0001;
+const E1: usize = 0b0010;
+const E2: usize = 0b0100;
+const E3: usize = 0b1000;
+const E01: usize = E0 | E1;
+const E02: usize = E0 | E2;
+const E03: usize = E0 | E3;
+const E12: usize = E1 | E2;
+const E13: usize = E1 | E3;
+const E23: usize = E2 | E3;
+const E012: usize = E0 | E1 | E2;
+const E013: usize = E0 | E1 | E3;
+const E023: usize = E0 | E2 | E3;
+const E123: usize = E1 | E2 | E3;
+const E0123: usize = 0b1111;
+
+/// The unit pseudoscalar `e0123`
+fn pseudoscalar() -> PgaMultivector {
+    let mut i = PgaMultivector::default();
+    i.0[E0123] = Float::ONE;
+    i
+}
+
+impl PgaMultivector {
+    fn scalar(s: Float) -> Self {
+        let mut m = Self::default();
+        m.0[0] = s;
+        m
+    }
+
+    /// The product of each pair of blades accepted by `keep`, where blades sharing `e0`
+    /// vanish as it squares to zero
+    fn product<F: Fn(usize, usize) -> bool>(&self, other: &Self, keep: F) -> Self {
+        let mut result = [Float::ZERO; 16];
+        for i in (0..16).filter(|i| self.0[*i] != Float::ZERO) {
+            for j in (0..16).filter(|j| i & j & E0 == 0 && keep(i, *j)) {
+                result[i ^ j] += reorder_sign(i, j) * self.0[i] * other.0[j];
+            }
+        }
+        Self(result)
+    }
+
+    fn geometric(&self, other: &Self) -> Self {
+        self.product(other, |_, _| true)
+    }
+
+    fn outer(&self, other: &Self) -> Self {
+        self.product(other, |a, b| a & b == 0)
+    }
+
+    /// The regressive product, the outer product of the duals
+    fn regressive(&self, other: &Self) -> Self {
+        self.dual().outer(&other.dual()).undual()
+    }
+
+    /// The Poincaré dual, mapping each blade to its complement such that `e ∧ dual(e) = e0123`
+    fn dual(&self) -> Self {
+        Self(std::array::from_fn(|i| {
+            self.0[E0123 ^ i] * reorder_sign(E0123 ^ i, i)
+        }))
+    }
+
+    fn undual(&self) -> Self {
+        Self(std::array::from_fn(|i| {
+            self.0[E0123 ^ i] * reorder_sign(i, E0123 ^ i)
+        }))
+    }
+
+    fn reversed(&self) -> Self {
+        Self(std::array::from_fn(|i| match i.count_ones() {
+            2 | 3 => -self.0[i],
+            _ => self.0[i],
+        }))
+    }
+
+    fn scaled(&self, scale: Float) -> Self {
+        Self(self.0.map(|value| value * scale))
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+
+    /// The magnitude of the Euclidean bivector part
+    fn euclidean_magnitude(&self) -> Float {
+        Bivector::new(self.0[E12], self.0[E13], self.0[E23]).magnitude()
+    }
+}
+
+/// The exponential of a bivector, a motor
+///
+/// A bivector `B` factors into `(a + b I) L` with `L` a unit line, and the commuting parts
+/// give `exp(B) = (cos a + sin a L)(1 + b I L)`
+fn exp(bivector: &PgaMultivector) -> PgaMultivector {
+    let a = bivector.euclidean_magnitude();
+    if a <= Float::from(TOLERANCE) {
+        // A pure translation squares to zero
+        return PgaMultivector::scalar(Float::ONE).add(bivector);
+    }
+
+    // B² = -a² - 2ab I
+    let square = bivector.geometric(bivector);
+    let b = -square.0[E0123] / (Float::from(2.0) * a);
+
+    // L = B (a + b I)⁻¹ = B (1 / a - b I / a²)
+    let mut inverse = PgaMultivector::scalar(Float::ONE / a);
+    inverse.0[E0123] = -b / (a * a);
+    let line = bivector.geometric(&inverse);
+
+    let rotation = PgaMultivector::scalar(a.cos()).add(&line.scaled(a.sin()));
+    let translation =
+        PgaMultivector::scalar(Float::ONE).add(&pseudoscalar().geometric(&line).scaled(b));
+    rotation.geometric(&translation)
+}
+
+/// The logarithm of a normalized motor with a non-negative scalar part, the inverse of [exp]
+fn log(motor: &PgaMultivector) -> PgaMultivector {
+    let mut bivector = *motor;
+    bivector.0[0] = Float::ZERO;
+    bivector.0[E0123] = Float::ZERO;
+
+    let (c, s) = (motor.0[0], bivector.euclidean_magnitude());
+    if s <= Float::from(TOLERANCE) {
+        return bivector.scaled(Float::ONE / c);
+    }
+
+    // M = c + (s + c b I) L - s b I
+    let a = Float::atan2(s, c);
+    let b = -motor.0[E0123] / s;
+
+    // L = <M>₂ (s + c b I)⁻¹
+    let mut inverse = PgaMultivector::scalar(Float::ONE / s);
+    inverse.0[E0123] = -c * b / (s * s);
+    let line = bivector.geometric(&inverse);
+
+    let mut scale = PgaMultivector::scalar(a);
+    scale.0[E0123] = b;
+    scale.geometric(&line)
+}
+
+impl From<PgaPlane> for PgaMultivector {
+    fn from(plane: PgaPlane) -> Self {
+        let mut m = Self::default();
+        m.0[E0] = plane.d;
+        m.0[E1] = plane.a;
+        m.0[E2] = plane.b;
+        m.0[E3] = plane.c;
+        m
+    }
+}
+
+impl From<PgaMultivector> for PgaPlane {
+    fn from(m: PgaMultivector) -> Self {
+        Self::new(m.0[E1], m.0[E2], m.0[E3], m.0[E0])
+    }
+}
+
+impl From<PgaLine> for PgaMultivector {
+    fn from(line: PgaLine) -> Self {
+        let b = Bivector::from_axis_vector(line.direction);
+        let mut m = Self::default();
+        m.0[E12] = b.xy;
+        m.0[E13] = b.xz;
+        m.0[E23] = b.yz;
+        m.0[E01] = line.moment.x;
+        m.0[E02] = line.moment.y;
+        m.0[E03] = line.moment.z;
+        m
+    }
+}
+
+impl From<PgaMultivector> for PgaLine {
+    fn from(m: PgaMultivector) -> Self {
+        let b = Bivector::new(m.0[E12], m.0[E13], m.0[E23]);
+        Self::new(
+            b.to_axis_vector(),
+            Vector::new(m.0[E01], m.0[E02], m.0[E03]),
+        )
+    }
+}
+
+impl From<PgaPoint> for PgaMultivector {
+    fn from(point: PgaPoint) -> Self {
+        let mut m = Self::default();
+        m.0[E023] = -point.x;
+        m.0[E013] = point.y;
+        m.0[E012] = -point.z;
+        m.0[E123] = point.w;
+        m
+    }
+}
+
+impl From<PgaMultivector> for PgaPoint {
+    fn from(m: PgaMultivector) -> Self {
+        Self::new(-m.0[E023], m.0[E013], -m.0[E012], m.0[E123])
+    }
+}
+
+impl From<Motor> for PgaMultivector {
+    fn from(motor: Motor) -> Self {
+        let mut m = Self::default();
+        m.0[0] = motor.s;
+        m.0[E12] = motor.b.xy;
+        m.0[E13] = motor.b.xz;
+        m.0[E23] = motor.b.yz;
+        m.0[E01] = motor.t.x;
+        m.0[E02] = motor.t.y;
+        m.0[E03] = motor.t.z;
+        m.0[E0123] = motor.p;
+        m
+    }
+}
+
+impl From<PgaMultivector> for Motor {
+    fn from(m: PgaMultivector) -> Self {
+        Self {
+            s: m.0[0],
+            b: Bivector::new(m.0[E12], m.0[E13], m.0[E23]),
+            t: Vector::new(m.0[E01], m.0[E02], m.0[E03]),
+            p: m.0[E0123],
+        }
+    }
+}
+
+#[cfg(test)]
+mod pga_tests {
+    use super::*;
+    use crate::constant::PI;
+    use crate::traits::Coincident;
+    use crate::traits::Intersects;
+
+    const EPSILON: Float = 1e-9;
+
+    fn point(x: Float, y: Float, z: Float) -> PgaPoint {
+        PgaPoint::from(Point::new(x, y, z))
+    }
+
+    fn assert_same_point(a: &PgaPoint, b: &Point) {
+        assert!(
+            a.to_point().unwrap().approximately(b, EPSILON),
+            "{a:?} {b:?}"
+        );
+    }
+
+    #[test]
+    fn join_points() {
+        let line = point(0.0, 1.0, 0.0).join(&point(2.0, 1.0, 0.0));
+        assert!(line
+            .direction
+            .approximately(Vector::new(2.0, 0.0, 0.0), EPSILON));
+        assert!(line
+            .moment
+            .approximately(Vector::new(0.0, 0.0, -2.0), EPSILON));
+
+        let expected = Line::new(Point::new(5.0, 1.0, 0.0), Vector::unit_x());
+        assert!(line.to_line().unwrap().coincident(&expected));
+
+        let plane = PgaPlane::from_points(
+            &point(0.0, 0.0, 1.0),
+            &point(1.0, 0.0, 1.0),
+            &point(0.0, 1.0, 1.0),
+        );
+        let plane = plane.to_plane().unwrap();
+        assert!(plane.norm.approximately(Vector::unit_z(), EPSILON));
+        assert!(plane.dist.approximately(1.0, EPSILON));
+    }
+
+    #[test]
+    fn meet_planes() {
+        let a = PgaPlane::from(Plane::new(Vector::unit_x(), 1.0));
+        let b = PgaPlane::from(Plane::new(Vector::unit_y(), 2.0));
+        let c = PgaPlane::from(Plane::new(Vector::unit_z(), 3.0));
+
+        let line = a.meet(&b);
+        assert!(line.direction.approximately(Vector::unit_z(), EPSILON));
+        let origin = line.closest_point_to_origin().unwrap();
+        assert!(origin.approximately(&Point::new(1.0, 2.0, 0.0), EPSILON));
+
+        assert_same_point(
+            &PgaPoint::from_planes(&a, &b, &c),
+            &Point::new(1.0, 2.0, 3.0),
+        );
+        assert_same_point(&line.meet(&c), &Point::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn meet_matches_existing_intersections() {
+        let plane = Plane::new(Vector::new(1.0, 2.0, -1.0).normalized(), 0.5);
+        let line = Line::new(Point::new(1.0, -1.0, 2.0), Vector::new(0.5, 1.0, 1.0));
+        let expected = plane.intersection(&line).unwrap();
+        let found = PgaPlane::from(plane).meet(&PgaLine::from(line.clone()));
+        assert_same_point(&found, &expected);
+
+        // Parallel lines meet the plane at infinity
+        let parallel = PgaLine::from_point_and_direction(Point::origin(), Vector::unit_x());
+        let ideal = PgaPlane::from(Plane::new(Vector::unit_z(), 1.0)).meet(&parallel);
+        assert!(ideal.is_ideal());
+        assert!(ideal.to_point().is_none());
+    }
+
+    #[test]
+    fn plane_line_point_incidence() {
+        let line = PgaLine::from_point_and_direction(Point::new(1.0, 2.0, 3.0), Vector::unit_y());
+        let plane = line.join(&point(0.0, 0.0, 0.0));
+        for p in [
+            Point::new(1.0, 2.0, 3.0),
+            Point::new(1.0, -5.0, 3.0),
+            Point::origin(),
+        ] {
+            assert!(plane.signed_distance(&p).approximately(0.0, EPSILON));
+        }
+        assert!(point(0.0, 0.0, 0.0)
+            .join(&line)
+            .approximately(plane, EPSILON));
+    }
+
+    #[test]
+    fn motor_matches_rotor_and_translation() {
+        let rotor = Rotor::from_axis_angle(Vector::new(1.0, -1.0, 2.0), 1.2);
+        let translation = Vector::new(3.0, -2.0, 0.5);
+        let motor = Motor::from_rotor_and_translation(rotor, translation);
+
+        let p = Point::new(0.3, 1.0, -2.0);
+        let expected = Point::origin() + p.as_vector().rotated_by(&rotor) + translation;
+        assert!(motor.transform(&p).approximately(&expected, EPSILON));
+
+        let v = Vector::new(1.0, 2.0, 3.0);
+        assert!(motor
+            .transform_vector(&v)
+            .approximately(v.rotated_by(&rotor), EPSILON));
+        assert!(motor.rotor().approximately(rotor, EPSILON));
+        assert!(motor.translation().approximately(translation, EPSILON));
+    }
+
+    #[test]
+    fn motor_composition() {
+        let a = Motor::from_rotor_and_translation(
+            Rotor::from_axis_angle(Vector::unit_z(), 0.7),
+            Vector::new(1.0, 0.0, 0.0),
+        );
+        let b = Motor::from_screw(
+            &PgaLine::from_point_and_direction(Point::new(0.0, 2.0, 0.0), Vector::unit_x()),
+            2.0,
+            -1.0,
+        );
+
+        let p = Point::new(0.5, -1.0, 2.0);
+        let sequential = a.transform(&b.transform(&p));
+        assert!((a * b).transform(&p).approximately(&sequential, EPSILON));
+
+        let inverse = (a * b).reversed();
+        assert!(inverse.transform(&sequential).approximately(&p, EPSILON));
+    }
+
+    #[test]
+    fn screw_motion() {
+        let axis = PgaLine::from_point_and_direction(Point::new(1.0, 0.0, 0.0), Vector::unit_z());
+        let motor = Motor::from_screw(&axis, PI, 2.0);
+        let moved = motor.transform(&Point::origin());
+        assert!(moved.approximately(&Point::new(2.0, 0.0, 2.0), EPSILON));
+
+        // A quarter turn about the axis is right-handed
+        let quarter = Motor::from_screw(&axis, PI / 2.0, 0.0);
+        let moved = quarter.transform(&Point::origin());
+        assert!(moved.approximately(&Point::new(1.0, -1.0, 0.0), EPSILON));
+    }
+
+    #[test]
+    fn transforming_elements_preserves_incidence() {
+        let motor = Motor::from_screw(
+            &PgaLine::new(Vector::new(1.0, 1.0, 0.0), Vector::new(0.0, 0.0, 1.0)),
+            0.9,
+            1.5,
+        );
+        let (a, b, c) = (
+            point(1.0, 0.0, 0.0),
+            point(0.0, 2.0, 1.0),
+            point(-1.0, 1.0, 3.0),
+        );
+
+        let line = motor.transform_line(&a.join(&b));
+        let joined = motor.transform_point(&a).join(&motor.transform_point(&b));
+        assert!(line.approximately(joined, EPSILON));
+
+        let plane = motor.transform_plane(&PgaPlane::from_points(&a, &b, &c));
+        let spanned = PgaPlane::from_points(
+            &motor.transform_point(&a),
+            &motor.transform_point(&b),
+            &motor.transform_point(&c),
+        );
+        assert!(plane.approximately(spanned, EPSILON));
+    }
+
+    #[test]
+    fn interpolation_follows_screw() {
+        let axis = PgaLine::from_point_and_direction(Point::new(0.0, 1.0, 0.0), Vector::unit_x());
+        let start = Motor::from_translation(Vector::new(0.0, 0.0, 1.0));
+        let screw = Motor::from_screw(&axis, 2.4, 3.0);
+        let end = screw * start;
+
+        let p = Point::new(0.5, 0.0, 0.0);
+        for weight in [0.0, 0.25, 0.5, 1.0] {
+            let partial = Motor::from_screw(&axis, 2.4 * weight, 3.0 * weight);
+            let expected = (partial * start).transform(&p);
+            let found = start.interpolate(&end, weight).transform(&p);
+            assert!(found.approximately(&expected, EPSILON));
+        }
+
+        // The shortest rotation is taken even when the end motor is negated
+        let flipped = Motor::from(PgaMultivector::from(end).scaled(-1.0));
+        let halfway = start.interpolate(&flipped, 0.5).transform(&p);
+        let expected = (Motor::from_screw(&axis, 1.2, 1.5) * start).transform(&p);
+        assert!(halfway.approximately(&expected, EPSILON));
+
+        // Pure translations interpolate linearly
+        let moved = Motor::from_translation(Vector::new(4.0, 0.0, 0.0));
+        let halfway = Motor::identity().interpolate(&moved, 0.5);
+        assert!(halfway
+            .translation()
+            .approximately(Vector::new(2.0, 0.0, 0.0), EPSILON));
+    }
+
+    #[test]
+    fn normalization() {
+        let motor = Motor::from_screw(
+            &PgaLine::from_point_and_direction(Point::new(1.0, 2.0, 3.0), Vector::unit_y()),
+            1.0,
+            2.0,
+        );
+        let scaled = Motor::from(PgaMultivector::from(motor).scaled(3.0));
+        assert!(scaled.normalized().approximately(motor, EPSILON));
+    }
+}
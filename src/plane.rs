@@ -31,7 +31,7 @@ pub const PLANE_YZ: Plane = Plane {
     dist: Float::ZERO,
 };
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub struct Plane {
     pub norm: Vector,
     pub dist: Float,
@@ -86,7 +86,7 @@ impl Plane {
         let (norm1, dist1) = (&self.norm, &self.dist);
         let (norm2, dist2) = (&other.norm, &other.dist);
 
-        let num = (dist1 * norm2.cross(&direction)) + (dist2 * norm1.cross(&direction));
+        let num = (dist1 * norm2.cross(&direction)) + (dist2 * direction.cross(norm1));
         let den = direction.dot(&direction);
 
         if den.abs().approximately(0.0, EPSILON) {
@@ -161,7 +161,7 @@ impl Intersects for Plane {
         let (norm1, dist1) = (&self.norm, &self.dist);
         let (norm2, dist2) = (&other.norm, &other.dist);
 
-        let num = (dist1 * norm2.cross(&direction)) + (dist2 * norm1.cross(&direction));
+        let num = (dist1 * norm2.cross(&direction)) + (dist2 * direction.cross(norm1));
         let den = direction.dot(&direction);
 
         if den.abs().approximately(0.0, EPSILON) {
@@ -183,15 +183,16 @@ impl Intersects<Line> for Plane {
     fn intersection(&self, other: &Line) -> Self::Intersection {
         let denom = self.norm.dot(&other.direction);
         if denom.abs().approximately(0.0, EPSILON) {
-            None // Ray is parallel to the plane
+            None // Line is parallel to the plane
         } else {
             let t = (self.dist - self.norm.dot(&other.origin.as_vector())) / denom;
-            (t >= Float::ZERO).then_some(other.origin + (other.direction.normalized() * t))
+            Some(other.origin + other.direction * t)
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod plane_tests {
     use crate::vec::X_AXIS;
     use crate::vec::Y_AXIS;
@@ -305,4 +306,29 @@ mod plane_tests {
             "Intersection directions differ between the original and inverted plane cases."
         );
     }
+
+    #[test]
+    fn test_line_intersection_behind_origin() {
+        // Lines are infinite, so the plane is hit even though it lies behind the origin
+        let plane = Plane::new(Z_AXIS, -Float::from(2.0));
+        let line = Line::new(Point::new(1.0, 0.0, 0.0), Vector::new(0.0, 0.0, 2.0));
+        let intersect = plane
+            .intersection(&line)
+            .expect("Expected an intersection between line and plane");
+
+        assert!(intersect.approximately(&Point::new(1.0, 0.0, -2.0), EPSILON));
+    }
+
+    #[test]
+    fn test_line_intersection_offset_planes() {
+        let plane1 = Plane::new(Z_AXIS, Float::from(2.0));
+        let plane2 = Plane::new(X_AXIS, -Float::from(3.0));
+        let intersect = plane1
+            .intersection(&plane2)
+            .expect("Expected an intersection between offset planes");
+
+        assert!(approx_eq(plane1.distance_to(intersect.origin), Float::ZERO));
+        assert!(approx_eq(plane2.distance_to(intersect.origin), Float::ZERO));
+        assert!(intersect.direction.parallel(&Y_AXIS));
+    }
 }
@@ -0,0 +1,324 @@
+//!
+//! Rays in 3D space
+//!
+//! Unlike a [crate::line::Line], which extends infinitely in both directions, a [Ray] only
+//! covers the parameter range `t_min..=t_max` along its direction. Casting a ray against a
+//! shape is done through [Intersects], with a [RayHit] describing the nearest hit in range
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::aabb::Aabb;
+use crate::plane::Plane;
+use crate::sphere::Sphere;
+use crate::traits::Approximately;
+use crate::traits::FloatExt;
+use crate::traits::Intersects;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+
+const EPSILON: Float = Float::EPSILON;
+
+/// A half-infinite or bounded ray, covering the points `origin + direction * t` for `t_min <= t <= t_max`
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Ray {
+    pub origin: Point,
+    pub direction: Vector,
+    pub t_min: Float,
+    pub t_max: Float,
+}
+
+/// The nearest point at which a [Ray] hits a shape
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct RayHit {
+    /// The ray parameter of the hit, in units of the ray direction
+    pub t: Float,
+    pub point: Point,
+    /// The unit surface normal at the hit. Closed shapes give the outward normal, flat
+    /// shapes give the normal of the side facing the ray
+    pub normal: Vector,
+}
+
+impl Ray {
+    /// Constructs a new [Ray] starting at `origin` and extending infinitely along `direction`
+    pub fn new(origin: Point, direction: Vector) -> Self {
+        Self {
+            origin,
+            direction,
+            t_min: Float::ZERO,
+            t_max: Float::INFINITY,
+        }
+    }
+
+    /// Returns a copy of this [Ray] limited to the parameter range `t_min..=t_max`
+    pub fn with_range<F: Into<Float>>(&self, t_min: F, t_max: F) -> Self {
+        Self {
+            t_min: t_min.into(),
+            t_max: t_max.into(),
+            ..*self
+        }
+    }
+
+    /// Returns the point at parameter `t` along the ray, regardless of its range
+    #[inline]
+    pub fn at<F: Into<Float>>(&self, t: F) -> Point {
+        self.origin + self.direction * t.into()
+    }
+
+    /// Test whether the parameter `t` lies within the range of the ray
+    #[inline]
+    pub fn in_range(&self, t: Float) -> bool {
+        t >= self.t_min && t <= self.t_max
+    }
+
+    /// Casts this ray against `target`, returning the nearest hit within range
+    ///
+    /// Equivalent to `target.intersection(&ray)`
+    pub fn cast<T>(&self, target: &T) -> Option<RayHit>
+    where
+        T: Intersects<Ray, Intersection = Option<RayHit>>,
+    {
+        target.intersection(self)
+    }
+
+    /// Builds a [RayHit] at parameter `t`, turning `normal` to face against the ray
+    pub(crate) fn facing_hit(&self, t: Float, normal: Vector) -> RayHit {
+        let normal = normal.normalized();
+        let normal = if normal.dot(&self.direction) > Float::ZERO {
+            -normal
+        } else {
+            normal
+        };
+        RayHit {
+            t,
+            point: self.at(t),
+            normal,
+        }
+    }
+}
+
+impl Intersects<Ray> for Plane {
+    type Intersection = Option<RayHit>;
+
+    fn interesects(&self, other: &Ray) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    fn intersection(&self, other: &Ray) -> Self::Intersection {
+        let denom = self.norm.dot(&other.direction);
+        if denom.abs().approximately(Float::ZERO, EPSILON) {
+            return None;
+        }
+
+        let t = (self.dist - self.norm.dot(&other.origin.as_vector())) / denom;
+        other.in_range(t).then(|| other.facing_hit(t, self.norm))
+    }
+}
+
+impl Intersects<Ray> for Sphere {
+    type Intersection = Option<RayHit>;
+
+    fn interesects(&self, other: &Ray) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Finds where the ray enters the sphere, or where it leaves if it starts inside
+    fn intersection(&self, other: &Ray) -> Self::Intersection {
+        let offset = other.origin - self.center;
+        let a = other.direction.length_sq();
+        if a == Float::ZERO {
+            return None;
+        }
+
+        let half_b = offset.dot(&other.direction);
+        let c = offset.length_sq() - self.radius * self.radius;
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < Float::ZERO {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+        let near = (-half_b - root) / a;
+        let far = (-half_b + root) / a;
+        let t = [near, far].into_iter().find(|t| other.in_range(*t))?;
+
+        let point = other.at(t);
+        Some(RayHit {
+            t,
+            point,
+            normal: (point - self.center).normalized(),
+        })
+    }
+}
+
+impl Intersects<Ray> for Aabb {
+    type Intersection = Option<RayHit>;
+
+    fn interesects(&self, other: &Ray) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Finds where the ray enters the box, or where it leaves if it starts inside
+    fn intersection(&self, other: &Ray) -> Self::Intersection {
+        let origin = other.origin.as_vector();
+        let origin = [origin.x, origin.y, origin.z];
+        let direction = [other.direction.x, other.direction.y, other.direction.z];
+        let min = [self.min.x, self.min.y, self.min.z];
+        let max = [self.max.x, self.max.y, self.max.z];
+
+        // Track the latest entry and earliest exit across the three slabs, along with
+        // the axis and side each happens on
+        let mut enter = (Float::NEG_INFINITY, 0, Float::ZERO);
+        let mut exit = (Float::INFINITY, 0, Float::ZERO);
+        for axis in 0..3 {
+            if direction[axis] == Float::ZERO {
+                if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                    return None;
+                }
+                continue;
+            }
+
+            let inv = Float::ONE / direction[axis];
+            let t0 = (min[axis] - origin[axis]) * inv;
+            let t1 = (max[axis] - origin[axis]) * inv;
+            let (near, far, side) = if t0 < t1 {
+                (t0, t1, -Float::ONE)
+            } else {
+                (t1, t0, Float::ONE)
+            };
+
+            if near > enter.0 {
+                enter = (near, axis, side);
+            }
+            if far < exit.0 {
+                exit = (far, axis, -side);
+            }
+        }
+
+        // A ray without direction never leaves the box, so is not considered to hit it
+        if enter.0 > exit.0 || exit.0 == Float::INFINITY {
+            return None;
+        }
+
+        let (t, axis, side) = [enter, exit]
+            .into_iter()
+            .find(|(t, _, _)| other.in_range(*t))?;
+
+        let mut normal = [Float::ZERO; 3];
+        normal[axis] = side;
+        Some(RayHit {
+            t,
+            point: other.at(t),
+            normal: Vector::new(normal[0], normal[1], normal[2]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod ray_tests {
+    use super::*;
+    use crate::circle::Circle;
+
+    const EPSILON: Float = 1e-9;
+
+    #[test]
+    fn range() {
+        let ray = Ray::new(Point::origin(), Vector::unit_x()).with_range(1.0, 2.0);
+        assert!(!ray.in_range(0.5));
+        assert!(ray.in_range(1.5));
+        assert!(!ray.in_range(2.5));
+        assert_eq!(ray.at(3.0), Point::new(3.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn plane_hit() {
+        let plane = Plane::new(Vector::unit_z(), 2.0);
+        let ray = Ray::new(Point::origin(), Vector::new(0.0, 1.0, 1.0));
+
+        let hit = ray.cast(&plane).unwrap();
+        assert!(hit.t.approximately(2.0, EPSILON));
+        assert!(hit.point.approximately(&Point::new(0.0, 2.0, 2.0), EPSILON));
+        assert!(hit.normal.approximately(-Vector::unit_z(), EPSILON));
+    }
+
+    #[test]
+    fn plane_behind_or_out_of_range() {
+        let plane = Plane::new(Vector::unit_z(), -2.0);
+        let ray = Ray::new(Point::origin(), Vector::unit_z());
+        assert!(!plane.interesects(&ray));
+        assert!(plane.interesects(&ray.with_range(-5.0, 5.0)));
+        assert!(!plane.interesects(&ray.with_range(-1.0, 5.0)));
+
+        let parallel = Ray::new(Point::origin(), Vector::unit_x());
+        assert!(ray.cast(&Plane::new(Vector::unit_z(), 0.0)).is_some());
+        assert!(parallel.cast(&plane).is_none());
+    }
+
+    #[test]
+    fn sphere_hit_from_outside() {
+        let sphere = Sphere::new(Point::new(5.0, 0.0, 0.0), 1.0);
+        let ray = Ray::new(Point::origin(), Vector::unit_x());
+
+        let hit = ray.cast(&sphere).unwrap();
+        assert!(hit.t.approximately(4.0, EPSILON));
+        assert!(hit.normal.approximately(-Vector::unit_x(), EPSILON));
+    }
+
+    #[test]
+    fn sphere_hit_from_inside() {
+        let sphere = Sphere::new(Point::origin(), 2.0);
+        let ray = Ray::new(Point::origin(), Vector::unit_y());
+
+        let hit = ray.cast(&sphere).unwrap();
+        assert!(hit.t.approximately(2.0, EPSILON));
+        assert!(hit.normal.approximately(Vector::unit_y(), EPSILON));
+    }
+
+    #[test]
+    fn sphere_miss() {
+        let sphere = Sphere::new(Point::new(5.0, 3.0, 0.0), 1.0);
+        let ray = Ray::new(Point::origin(), Vector::unit_x());
+        assert!(!sphere.interesects(&ray));
+
+        let short = Ray::new(Point::origin(), Vector::unit_y()).with_range(0.0, 1.0);
+        assert!(!Sphere::new(Point::new(0.0, 5.0, 0.0), 1.0).interesects(&short));
+    }
+
+    #[test]
+    fn aabb_hit() {
+        let aabb = Aabb::new(Point::new(1.0, -1.0, -1.0), Point::new(3.0, 1.0, 1.0));
+        let ray = Ray::new(Point::origin(), Vector::new(1.0, 0.25, 0.0));
+
+        let hit = ray.cast(&aabb).unwrap();
+        assert!(hit.t.approximately(1.0, EPSILON));
+        assert!(hit.normal.approximately(-Vector::unit_x(), EPSILON));
+
+        let inside = Ray::new(Point::new(2.0, 0.0, 0.0), -Vector::unit_z());
+        let hit = inside.cast(&aabb).unwrap();
+        assert!(hit.t.approximately(1.0, EPSILON));
+        assert!(hit.normal.approximately(-Vector::unit_z(), EPSILON));
+    }
+
+    #[test]
+    fn aabb_miss() {
+        let aabb = Aabb::new(Point::new(1.0, -1.0, -1.0), Point::new(3.0, 1.0, 1.0));
+        assert!(!aabb.interesects(&Ray::new(Point::origin(), Vector::unit_y())));
+        assert!(!aabb.interesects(&Ray::new(Point::origin(), -Vector::unit_x())));
+        assert!(!aabb.interesects(&Ray::new(Point::new(0.0, 2.0, 0.0), Vector::unit_x())));
+    }
+
+    #[test]
+    fn circle_hit() {
+        let circle = Circle::new(Point::new(0.0, 0.0, 3.0), Vector::unit_z(), 1.0);
+        let ray = Ray::new(Point::new(0.5, 0.0, 0.0), Vector::unit_z());
+
+        let hit = ray.cast(&circle).unwrap();
+        assert!(hit.t.approximately(3.0, EPSILON));
+        assert!(hit.normal.approximately(-Vector::unit_z(), EPSILON));
+
+        let outside = Ray::new(Point::new(1.5, 0.0, 0.0), Vector::unit_z());
+        assert!(!circle.interesects(&outside));
+    }
+}
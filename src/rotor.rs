@@ -3,6 +3,7 @@
 //!
 
 use crate::bivec::Bivector;
+use crate::matrix::Matrix;
 use crate::traits::FloatExt;
 use crate::Approximately;
 use crate::Float;
@@ -15,6 +16,36 @@ use std::ops::Mul;
 // with. Basically, you just constrain the real part to be positive which fixes your interpolation
 // on one half of the Lie-manifold which ensures the arc taken is as short as possible.
 
+/// The order in which Euler angles are applied
+///
+/// Each variant names the axes in the order their rotations are applied, each about the
+/// fixed world axis. [EulerOrder::XYZ] rotates about x, then y, then z, which is the same
+/// as rotating about the local z, then y, then x axes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EulerOrder {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+impl EulerOrder {
+    /// The indices of the axes in the order they are applied, and whether that order is
+    /// an even permutation of x, y, z
+    fn axes(&self) -> ([usize; 3], bool) {
+        match self {
+            EulerOrder::XYZ => ([0, 1, 2], true),
+            EulerOrder::YZX => ([1, 2, 0], true),
+            EulerOrder::ZXY => ([2, 0, 1], true),
+            EulerOrder::XZY => ([0, 2, 1], false),
+            EulerOrder::YXZ => ([1, 0, 2], false),
+            EulerOrder::ZYX => ([2, 1, 0], false),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct Rotor {
@@ -117,6 +148,174 @@ impl Rotor {
         }
     }
 
+    /// Returns a new `Rotor` for a right-handed rotation of `angle` radians about `axis`
+    ///
+    /// ```
+    /// # use integrator::{ traits::Approximately, vec::Vector, rotor::Rotor, constant::PI };
+    /// let rotor = Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0);
+    /// let rotated = Vector::unit_x().rotated_by(&rotor);
+    /// assert!(rotated.approximately(Vector::unit_y(), 1e-12));
+    /// ```
+    #[inline]
+    pub fn from_axis_angle<F: Into<Float>>(axis: Vector, angle: F) -> Self {
+        Self::from_angle_and_plane(angle, Bivector::from_axis_vector(axis.normalized()))
+    }
+
+    /// Returns the unit axis and the angle, in the range `0..=π`, of the right-handed rotation
+    /// represented by this [Rotor]
+    ///
+    /// The axis of the identity rotation is arbitrary, the x axis is returned
+    pub fn to_axis_angle(&self) -> (Vector, Float) {
+        let r = self.normalized().canonicalized();
+        let axis = -r.b.to_axis_vector();
+        let sin_half = axis.length();
+        if sin_half == Float::ZERO {
+            return (Vector::unit_x(), Float::ZERO);
+        }
+
+        let angle = Float::from(2.0) * Float::atan2(sin_half, r.s);
+        (axis / sin_half, angle)
+    }
+
+    /// Returns a new `Rotor` from Euler angles in radians, applied in the given `order`
+    ///
+    /// The angles are given in the same order as the axes named by `order`, so for
+    /// [EulerOrder::ZYX] `first` is the rotation about z
+    ///
+    /// ```
+    /// # use integrator::{ traits::Approximately, vec::Vector, rotor::{ Rotor, EulerOrder }, constant::PI };
+    /// let rotor = Rotor::from_euler_angles(EulerOrder::XYZ, PI / 2.0, 0.0, PI / 2.0);
+    /// // y is turned onto z about x, which then stays put when turning about z
+    /// let rotated = Vector::unit_y().rotated_by(&rotor);
+    /// assert!(rotated.approximately(Vector::unit_z(), 1e-12));
+    /// let rotated = Vector::unit_x().rotated_by(&rotor);
+    /// assert!(rotated.approximately(Vector::unit_y(), 1e-12));
+    /// ```
+    pub fn from_euler_angles<F: Into<Float>>(
+        order: EulerOrder,
+        first: F,
+        second: F,
+        third: F,
+    ) -> Self {
+        let (axes, _) = order.axes();
+        let angles = [first.into(), second.into(), third.into()];
+        let unit = [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()];
+
+        // Later rotations are applied on the left
+        (0..3).fold(Self::identity(), |rotor, i| {
+            Self::from_axis_angle(unit[axes[i]], angles[i]) * rotor
+        })
+    }
+
+    /// Returns the Euler angles in radians of this [Rotor] for the given `order`, see
+    /// [Rotor::from_euler_angles]
+    ///
+    /// The second angle is in the range `-π/2..=π/2` and the others in `-π..=π`. At gimbal
+    /// lock, when the second angle is `±π/2`, the first and third axes line up and only their
+    /// combined rotation is known, in which case the third angle is reported as zero
+    pub fn to_euler_angles(&self, order: EulerOrder) -> (Float, Float, Float) {
+        let (axes, even) = order.axes();
+        let (i, j, k) = (axes[0], axes[1], axes[2]);
+        let sign = if even { Float::ONE } else { -Float::ONE };
+
+        // Columns of the rotation matrix are the images of the axes
+        let columns = [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()]
+            .map(|axis| axis.rotated_by(self))
+            .map(|c| [c.x, c.y, c.z]);
+        let m = |row: usize, col: usize| columns[col][row];
+
+        // The cosine of the second angle is recovered from the other entries in its column, which
+        // stays accurate near ±π/2 where taking the arcsine alone would not
+        let cos_second = Float::sqrt(m(i, i) * m(i, i) + m(j, i) * m(j, i));
+        let second = Float::atan2(-sign * m(k, i), cos_second);
+
+        let gimbal_lock = cos_second < Float::from(1e-9);
+        if gimbal_lock {
+            let first = Float::atan2(-sign * m(j, k), m(j, j));
+            return (first, second, Float::ZERO);
+        }
+
+        let first = Float::atan2(sign * m(k, j), m(k, k));
+        let third = Float::atan2(sign * m(j, i), m(i, i));
+        (first, second, third)
+    }
+
+    /// Returns the orientation of a [Matrix] which transforms column vectors, laid out as by
+    /// [Rotor::to_matrix], [crate::isometry::Isometry::to_matrix] and
+    /// [crate::transform::Transform::to_matrix]
+    ///
+    /// Any translation and scale are ignored, see [Matrix::decompose_column_vector]. Returns
+    /// `None` if the matrix is projective, singular or sheared
+    pub fn from_matrix(matrix: &Matrix) -> Option<Self> {
+        matrix.decompose_column_vector().map(|d| d.orientation)
+    }
+
+    /// Returns the rotation [Matrix] of this [Rotor], which transforms column vectors so that
+    /// `&rotor.to_matrix() * &v` is `v.rotated_by(&rotor)`
+    ///
+    /// This is the transpose of the row-vector [Matrix::from_orientation]
+    #[inline]
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_orientation(*self).transposed()
+    }
+
+    /// Returns a new `Rotor` for the rotation swept out by the angular velocity `velocity` over `dt`
+    ///
+    /// This is the exponential map of `velocity * dt`. The plane of `velocity` is the plane of
+    /// rotation and its magnitude is the rate of rotation in radians per unit time
+    ///
+    /// ```
+    /// # use integrator::{ traits::{ Approximately, FloatExt }, Float, vec::Vector, rotor::Rotor, bivec::Bivector, constant::PI };
+    /// let rotor = Rotor::from_angular_velocity(Bivector::new(PI, 0.0, 0.0), 0.5);
+    /// let rotated = Vector::unit_x().rotated_by(&rotor);
+    /// assert!(rotated.approximately(Vector::unit_y(), Float::EPSILON));
+    /// ```
+    #[inline]
+    pub fn from_angular_velocity<F: Into<Float>>(velocity: Bivector, dt: F) -> Self {
+        let rate = velocity.magnitude();
+        if rate == Float::ZERO {
+            return Self::identity();
+        }
+
+        Self::from_angle_and_plane(rate * dt.into(), velocity / rate)
+    }
+
+    /// Advances this orientation by the angular velocity `velocity` over `dt`
+    ///
+    /// The angular velocity is expressed in world space, so the new rotation is applied
+    /// after the existing orientation. The result stays on the unit manifold without the
+    /// need for renormalization
+    #[inline]
+    pub fn advance<F: Into<Float>>(&mut self, velocity: Bivector, dt: F) {
+        *self = Self::from_angular_velocity(velocity, dt) * *self
+    }
+
+    /// Returns a new [Rotor] advanced by the angular velocity `velocity` over `dt`
+    ///
+    /// See [Rotor::advance]
+    #[inline]
+    pub fn advanced<F: Into<Float>>(&self, velocity: Bivector, dt: F) -> Self {
+        let mut advanced = *self;
+        advanced.advance(velocity, dt);
+        advanced
+    }
+
+    /// Advances this orientation by an angular velocity given as an axis [Vector] over `dt`
+    ///
+    /// The rotation is right-handed about `axis`, at a rate of `axis.length()` radians per unit time
+    #[inline]
+    pub fn advance_by_axis<F: Into<Float>>(&mut self, axis: Vector, dt: F) {
+        self.advance(Bivector::from_axis_vector(axis), dt)
+    }
+
+    /// Returns a new [Rotor] advanced by an angular velocity given as an axis [Vector] over `dt`
+    ///
+    /// See [Rotor::advance_by_axis]
+    #[inline]
+    pub fn advanced_by_axis<F: Into<Float>>(&self, axis: Vector, dt: F) -> Self {
+        self.advanced(Bivector::from_axis_vector(axis), dt)
+    }
+
     /// Computes and returns the geometric product of two [Rotor]'s
     #[inline]
     pub fn product(&self, other: &Self) -> Self {
@@ -179,6 +378,223 @@ impl Rotor {
         self.b.xz = -self.b.xz;
         self.b.yz = -self.b.yz;
     }
+
+    /// Returns the logarithm of this [Rotor], the inverse of [Bivector::exp]
+    ///
+    /// The result is the plane of rotation scaled by the angle of rotation, like an angular
+    /// velocity applied for one unit of time. The shortest rotation is chosen, so the
+    /// magnitude of the result is at most `π`
+    ///
+    /// ```
+    /// # use integrator::{ traits::Approximately, vec::Vector, rotor::Rotor };
+    /// let rotor = Rotor::from_axis_angle(Vector::new(1.0, 2.0, -1.0), 0.75);
+    /// assert!(rotor.log().exp().approximately(rotor, 1e-12));
+    /// ```
+    pub fn log(&self) -> Bivector {
+        let r = self.normalized().canonicalized();
+        let sin_half = r.b.magnitude();
+        if sin_half == Float::ZERO {
+            return Bivector::zero();
+        }
+
+        // The bivector part of a rotor is the negated plane scaled by the sine of half the angle
+        let angle = Float::from(2.0) * Float::atan2(sin_half, r.s);
+        r.b * (-angle / sin_half)
+    }
+
+    /// Returns the angle in radians of the shortest rotation represented by this [Rotor],
+    /// in the range `0..=π`
+    #[inline]
+    pub fn angle(&self) -> Float {
+        Float::from(2.0) * Float::atan2(self.b.magnitude(), self.s.abs())
+    }
+
+    /// Returns the unit plane of the shortest rotation represented by this [Rotor], such that
+    /// `Rotor::from_angle_and_plane(r.angle(), r.plane())` is the same rotation as `r`
+    ///
+    /// The identity rotation has no plane, and a zero [Bivector] is returned
+    pub fn plane(&self) -> Bivector {
+        let b = self.canonicalized().b;
+        let sin_half = b.magnitude();
+        if sin_half == Float::ZERO {
+            return Bivector::zero();
+        }
+
+        -b / sin_half
+    }
+
+    /// Returns the angle in radians of the shortest rotation from this orientation to `other`,
+    /// in the range `0..=π`
+    #[inline]
+    pub fn angle_to(&self, other: &Self) -> Float {
+        (other * self.reversed()).angle()
+    }
+
+    /// Splits this [Rotor] into a swing and a twist about `axis`, returned as `(swing, twist)`
+    ///
+    /// The twist is the part of the rotation about `axis`, and the swing is a rotation about an
+    /// axis perpendicular to it. Applying the twist and then the swing gives the original
+    /// rotation, `swing * twist`. When the rotation turns `axis` around by half a turn the twist
+    /// is undefined, and the identity is used
+    ///
+    /// ```
+    /// # use integrator::{ traits::Approximately, vec::Vector, rotor::Rotor };
+    /// let twist = Rotor::from_axis_angle(Vector::unit_y(), 0.5);
+    /// let swing = Rotor::from_axis_angle(Vector::unit_x(), 0.25);
+    /// let (s, t) = (swing * twist).swing_twist(Vector::unit_y());
+    /// assert!(s.approximately(swing, 1e-12));
+    /// assert!(t.approximately(twist, 1e-12));
+    /// ```
+    pub fn swing_twist(&self, axis: Vector) -> (Self, Self) {
+        let plane = Bivector::from_axis_vector(axis.normalized());
+        let twist = Self::new(plane * plane.dot(&self.b), self.s);
+
+        let twist = if twist.magnitude_sq() == Float::ZERO {
+            Self::identity()
+        } else {
+            twist.normalized()
+        };
+        (self * twist.reversed(), twist)
+    }
+
+    /// Computes the dot product of two [Rotor]'s, treated as four dimensional vectors
+    ///
+    /// For unit rotors this is the cosine of half the angle between the two orientations,
+    /// and it is negative when they lie on opposite halves of the manifold
+    #[inline]
+    pub fn dot(&self, other: &Self) -> Float {
+        self.s * other.s + self.b.xy * other.b.xy + self.b.xz * other.b.xz + self.b.yz * other.b.yz
+    }
+
+    /// Returns this [Rotor] with a non-negative scalar part
+    ///
+    /// `r` and `-r` represent the same rotation. Keeping the scalar part positive keeps rotors on
+    /// one half of the manifold, so that interpolating between them takes the shortest arc
+    #[inline]
+    pub fn canonicalized(&self) -> Self {
+        let mut canonicalized = *self;
+        canonicalized.canonicalize();
+        canonicalized
+    }
+
+    /// Flips this [Rotor] in place to have a non-negative scalar part, see [Rotor::canonicalized]
+    #[inline]
+    pub fn canonicalize(&mut self) {
+        if self.s < Float::ZERO {
+            *self = self.scaled(-Float::ONE);
+        }
+    }
+
+    /// Normalized linear interpolation from this [Rotor] to `to`, by the amount of `weight`
+    ///
+    /// Cheaper than [Rotor::slerp] and follows the same path, but does not rotate at a
+    /// constant rate. Takes the shortest arc between the two orientations
+    ///
+    /// ```
+    /// # use integrator::{ traits::Approximately, vec::Vector, rotor::Rotor, constant::PI };
+    /// let from = Rotor::identity();
+    /// let to = Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0);
+    /// let halfway = Vector::unit_x().rotated_by(&from.nlerp(&to, 0.5));
+    /// assert!(halfway.approximately(Vector::new(1.0, 1.0, 0.0).normalized(), 1e-12));
+    /// ```
+    pub fn nlerp<F: Into<Float>>(&self, to: &Self, weight: F) -> Self {
+        let w = weight.into();
+        let to = self.same_half(to);
+        Self::combine(self, Float::ONE - w, &to, w).normalized()
+    }
+
+    /// Spherical linear interpolation from this [Rotor] to `to`, by the amount of `weight`
+    ///
+    /// Rotates at a constant rate about a fixed axis, taking the shortest arc between the
+    /// two orientations
+    ///
+    /// ```
+    /// # use integrator::{ traits::Approximately, vec::Vector, rotor::Rotor, constant::PI };
+    /// let from = Rotor::identity();
+    /// let to = Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0);
+    /// let third = Vector::unit_x().rotated_by(&from.slerp(&to, 1.0 / 3.0));
+    /// assert!(third.approximately(Vector::new((PI / 6.0).cos(), (PI / 6.0).sin(), 0.0), 1e-12));
+    /// ```
+    pub fn slerp<F: Into<Float>>(&self, to: &Self, weight: F) -> Self {
+        self.slerp_along(&self.same_half(to), weight.into())
+    }
+
+    /// Computes the inner control point of the key `current` for [Rotor::squad], from its
+    /// neighbouring keys `previous` and `next`
+    ///
+    /// At the ends of a sequence of keys, pass the end key as its own missing neighbour
+    pub fn squad_control(previous: &Self, current: &Self, next: &Self) -> Self {
+        let inverse = current.reversed();
+        let to_next = (inverse * current.same_half(next)).log();
+        let to_previous = (inverse * current.same_half(previous)).log();
+
+        let tangent = (to_next + to_previous) / -Float::from(4.0);
+        current * tangent.exp()
+    }
+
+    /// Spherical cubic interpolation from this [Rotor] to `to`, by the amount of `weight`
+    ///
+    /// `from_control` and `to_control` are the control points of the two keys as given by
+    /// [Rotor::squad_control]. Interpolating each consecutive pair of a sequence of keys
+    /// this way gives a path with a continuous angular velocity through every key
+    pub fn squad<F: Into<Float>>(
+        &self,
+        to: &Self,
+        from_control: &Self,
+        to_control: &Self,
+        weight: F,
+    ) -> Self {
+        let w = weight.into();
+
+        // The control point of the target key has to be flipped along with it
+        let (to, to_control) = if self.dot(to) < Float::ZERO {
+            (to.scaled(-Float::ONE), to_control.scaled(-Float::ONE))
+        } else {
+            (*to, *to_control)
+        };
+
+        let keys = self.slerp_along(&to, w);
+        let controls = from_control.slerp_along(&to_control, w);
+        keys.slerp_along(&controls, Float::from(2.0) * w * (Float::ONE - w))
+    }
+
+    /// Slerp from this [Rotor] to `to` without choosing the shortest arc
+    fn slerp_along(&self, to: &Self, weight: Float) -> Self {
+        let cos = Float::min(Float::ONE, self.dot(to));
+
+        // Nearly parallel rotors fall back on nlerp, which is indistinguishable and stable
+        if cos > Float::ONE - Float::from(1e-9) {
+            return Self::combine(self, Float::ONE - weight, to, weight).normalized();
+        }
+
+        let angle = cos.acos();
+        let sin = angle.sin();
+        let from_weight = ((Float::ONE - weight) * angle).sin() / sin;
+        let to_weight = (weight * angle).sin() / sin;
+        Self::combine(self, from_weight, to, to_weight).normalized()
+    }
+
+    /// Returns `other`, flipped if needed to lie on the same half of the manifold as this [Rotor]
+    #[inline]
+    fn same_half(&self, other: &Self) -> Self {
+        if self.dot(other) < Float::ZERO {
+            other.scaled(-Float::ONE)
+        } else {
+            *other
+        }
+    }
+
+    /// Scales every component of this [Rotor], without normalizing
+    #[inline]
+    fn scaled(&self, scale: Float) -> Self {
+        Self::new(self.b * scale, self.s * scale)
+    }
+
+    /// The weighted sum `a * wa + b * wb` of two [Rotor]'s, without normalizing
+    #[inline]
+    fn combine(a: &Self, wa: Float, b: &Self, wb: Float) -> Self {
+        Self::new(a.b * wa + b.b * wb, a.s * wa + b.s * wb)
+    }
 }
 
 impl Mul<&Rotor> for &Rotor {
@@ -213,6 +629,13 @@ impl Mul for Rotor {
     }
 }
 
+impl From<Rotor> for Matrix {
+    /// The column-vector rotation [Matrix], see [Rotor::to_matrix]
+    fn from(rotor: Rotor) -> Self {
+        rotor.to_matrix()
+    }
+}
+
 impl std::fmt::Display for Rotor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -311,8 +734,11 @@ pub(crate) mod fixed {
 mod rotor_tests {
     use super::*;
     use crate::constant::PI;
+    use crate::isometry::Isometry;
     use crate::traits::Zero;
+    use crate::transform::Transform;
     use crate::Approximately;
+    use crate::Point;
     use crate::Vector;
 
     const EPSILON: Float = Float::EPSILON;
@@ -329,6 +755,338 @@ mod rotor_tests {
         assert!(v.approximately(test_vector(), EPSILON));
     }
 
+    fn assert_same_rotation(a: &Rotor, b: &Rotor) {
+        for axis in [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()] {
+            assert!(axis.rotated_by(a).approximately(axis.rotated_by(b), 1e-9));
+        }
+    }
+
+    const ORDERS: [EulerOrder; 6] = [
+        EulerOrder::XYZ,
+        EulerOrder::XZY,
+        EulerOrder::YXZ,
+        EulerOrder::YZX,
+        EulerOrder::ZXY,
+        EulerOrder::ZYX,
+    ];
+
+    #[test]
+    fn axis_angle_round_trip() {
+        let axis = Vector::new(1.0, -2.0, 0.5).normalized();
+        for angle in [0.1, 1.0, 2.5, PI - 1e-3] {
+            let rotor = Rotor::from_axis_angle(axis, angle);
+            let (out_axis, out_angle) = rotor.to_axis_angle();
+            assert!(out_axis.approximately(axis, 1e-9));
+            assert!(out_angle.approximately(angle, 1e-9));
+
+            // The same rotation the other way around the opposite axis
+            let (out_axis, out_angle) = Rotor::from_axis_angle(-axis, -angle).to_axis_angle();
+            assert!(out_axis.approximately(axis, 1e-9));
+            assert!(out_angle.approximately(angle, 1e-9));
+        }
+
+        let (axis, angle) = Rotor::identity().to_axis_angle();
+        assert!(axis.approximately(Vector::unit_x(), EPSILON));
+        assert!(angle.approximately(0.0, EPSILON));
+    }
+
+    #[test]
+    fn axis_angle_is_right_handed() {
+        let about_x = Rotor::from_axis_angle(Vector::unit_x(), PI / 2.0);
+        let about_y = Rotor::from_axis_angle(Vector::unit_y(), PI / 2.0);
+        let about_z = Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0);
+        assert!(Vector::unit_y()
+            .rotated_by(&about_x)
+            .approximately(Vector::unit_z(), 1e-12));
+        assert!(Vector::unit_z()
+            .rotated_by(&about_y)
+            .approximately(Vector::unit_x(), 1e-12));
+        assert!(Vector::unit_x()
+            .rotated_by(&about_z)
+            .approximately(Vector::unit_y(), 1e-12));
+    }
+
+    #[test]
+    fn euler_order_is_respected() {
+        let (a, b) = (0.3, -0.8);
+        let rx = Rotor::from_axis_angle(Vector::unit_x(), a);
+        let rz = Rotor::from_axis_angle(Vector::unit_z(), b);
+        let xz = Rotor::from_euler_angles(EulerOrder::XZY, a, b, 0.0);
+        let zx = Rotor::from_euler_angles(EulerOrder::ZXY, b, a, 0.0);
+        assert_same_rotation(&xz, &(rz * rx));
+        assert_same_rotation(&zx, &(rx * rz));
+    }
+
+    #[test]
+    fn euler_round_trip() {
+        let angles = [
+            (0.3, -0.7, 1.9),
+            (-2.5, 1.2, 0.4),
+            (3.0, 0.0, -3.0),
+            (0.0, 0.0, 0.0),
+        ];
+        for order in ORDERS {
+            for (a, b, c) in angles {
+                let rotor = Rotor::from_euler_angles(order, a, b, c);
+                let (x, y, z) = rotor.to_euler_angles(order);
+                assert!(x.approximately(a, 1e-9), "{order:?} {x} {a}");
+                assert!(y.approximately(b, 1e-9), "{order:?} {y} {b}");
+                assert!(z.approximately(c, 1e-9), "{order:?} {z} {c}");
+            }
+        }
+    }
+
+    #[test]
+    fn euler_gimbal_lock() {
+        for order in ORDERS {
+            for second in [PI / 2.0, -PI / 2.0] {
+                let rotor = Rotor::from_euler_angles(order, 0.4, second, 0.9);
+                let (x, y, z) = rotor.to_euler_angles(order);
+                assert!(y.approximately(second, 1e-6));
+                assert!(z.approximately(0.0, EPSILON));
+
+                // The angles found still describe the same rotation
+                let recovered = Rotor::from_euler_angles(order, x, y, z);
+                assert_same_rotation(&recovered, &rotor);
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        for order in ORDERS {
+            let rotor = Rotor::from_euler_angles(order, 0.3, -1.1, 2.7);
+            let matrix = Matrix::from(rotor);
+            let recovered = Rotor::from_matrix(&matrix).unwrap();
+            assert_same_rotation(&recovered, &rotor);
+
+            // The matrix rotates column vectors the same way as the rotor
+            let v = Vector::new(0.2, -0.4, 0.9);
+            assert!((&matrix * v).approximately(v.rotated_by(&rotor), 1e-9));
+        }
+
+        let half_turn = Rotor::from_axis_angle(Vector::new(1.0, 1.0, 0.0), PI);
+        let recovered = Rotor::from_matrix(&half_turn.to_matrix()).unwrap();
+        assert_same_rotation(&recovered, &half_turn);
+
+        assert!(Rotor::from_matrix(&Matrix::perspective(0.1, 10.0)).is_none());
+    }
+
+    #[test]
+    fn from_matrix_undoes_rigid_and_affine_matrices() {
+        let rotor = Rotor::from_axis_angle(Vector::unit_z(), 0.7);
+        for translation in [Vector::zero(), Vector::new(1.0, 2.0, 3.0)] {
+            let isometry = Isometry::new(rotor, translation);
+            let recovered = Rotor::from_matrix(&isometry.to_matrix()).unwrap();
+            assert_same_rotation(&recovered, &rotor);
+
+            let transform = Transform::new(translation, rotor, Vector::new(2.0, 1.0, 1.0));
+            let recovered = Rotor::from_matrix(&transform.to_matrix()).unwrap();
+            assert_same_rotation(&recovered, &rotor);
+        }
+
+        // Every conversion shares the column layout
+        let p = Point::new(1.0, 0.0, 0.0);
+        let rotated = &Matrix::from(rotor) * &p;
+        assert!(rotated.approximately(&(&Matrix::from(Isometry::from_rotation(rotor)) * &p), 1e-12));
+        assert!(rotated.y > 0.0);
+    }
+
+    #[test]
+    fn canonicalization() {
+        let rotor = Rotor::from_axis_angle(Vector::new(1.0, 2.0, 3.0), 1.0);
+        let flipped = rotor.scaled(-1.0);
+        assert!(flipped.s < 0.0);
+        assert!(flipped.canonicalized().approximately(rotor, EPSILON));
+        assert!(rotor.canonicalized().approximately(rotor, EPSILON));
+        assert_same_rotation(&flipped.canonicalized(), &flipped);
+    }
+
+    #[test]
+    fn slerp_is_constant_rate() {
+        let from = Rotor::from_axis_angle(Vector::unit_x(), 0.2);
+        let to = Rotor::from_axis_angle(Vector::unit_x(), 2.2);
+        for i in 0..=4 {
+            let w = i as Float / 4.0;
+            let expected = Rotor::from_axis_angle(Vector::unit_x(), 0.2 + 2.0 * w);
+            assert_same_rotation(&from.slerp(&to, w), &expected);
+        }
+    }
+
+    #[test]
+    fn interpolation_takes_shortest_arc() {
+        let from = Rotor::from_axis_angle(Vector::unit_z(), 0.1);
+        let to = Rotor::from_axis_angle(Vector::unit_z(), 0.5).scaled(-1.0);
+        let expected = Rotor::from_axis_angle(Vector::unit_z(), 0.3);
+        assert_same_rotation(&from.slerp(&to, 0.5), &expected);
+        assert_same_rotation(&from.nlerp(&to, 0.5), &expected);
+    }
+
+    #[test]
+    fn interpolation_end_points() {
+        let from = Rotor::from_axis_angle(Vector::new(1.0, 1.0, 0.0), 0.4);
+        let to = Rotor::from_axis_angle(Vector::new(0.0, -1.0, 2.0), 2.9);
+        for interpolated in [from.slerp(&to, 0.0), from.nlerp(&to, 0.0)] {
+            assert_same_rotation(&interpolated, &from);
+        }
+        for interpolated in [from.slerp(&to, 1.0), from.nlerp(&to, 1.0)] {
+            assert_same_rotation(&interpolated, &to);
+        }
+
+        let nearby = from * Rotor::from_axis_angle(Vector::unit_x(), 1e-10);
+        assert!(from
+            .slerp(&nearby, 0.5)
+            .magnitude()
+            .approximately(1.0, 1e-12));
+    }
+
+    #[test]
+    fn squad_passes_through_keys() {
+        let keys = [
+            Rotor::identity(),
+            Rotor::from_axis_angle(Vector::unit_z(), 1.0),
+            Rotor::from_axis_angle(Vector::new(1.0, 1.0, 0.0), 1.5),
+            Rotor::from_axis_angle(Vector::unit_y(), -0.5).scaled(-1.0),
+        ];
+        let control = |i: usize| {
+            Rotor::squad_control(&keys[i.saturating_sub(1)], &keys[i], &keys[(i + 1).min(3)])
+        };
+
+        for i in 0..3 {
+            let (from, to) = (keys[i], keys[i + 1]);
+            let (a, b) = (control(i), control(i + 1));
+            assert_same_rotation(&from.squad(&to, &a, &b, 0.0), &from);
+            assert_same_rotation(&from.squad(&to, &a, &b, 1.0), &to);
+        }
+    }
+
+    #[test]
+    fn squad_is_smooth_through_keys() {
+        let keys = [
+            Rotor::from_axis_angle(Vector::unit_x(), 0.3),
+            Rotor::from_axis_angle(Vector::unit_z(), 1.0),
+            Rotor::from_axis_angle(Vector::new(1.0, 1.0, 0.0), 1.5),
+        ];
+        let c0 = Rotor::squad_control(&keys[0], &keys[0], &keys[1]);
+        let c1 = Rotor::squad_control(&keys[0], &keys[1], &keys[2]);
+        let c2 = Rotor::squad_control(&keys[1], &keys[2], &keys[2]);
+
+        // The relative rotation over a small step either side of the middle key should match
+        let h = 1e-5;
+        let before = keys[0].squad(&keys[1], &c0, &c1, 1.0 - h);
+        let after = keys[1].squad(&keys[2], &c1, &c2, h);
+        let into = keys[1] * before.reversed();
+        let out = after * keys[1].reversed();
+        assert!(into.approximately(out, 1e-8));
+
+        // Whereas piecewise slerp has a corner at the key
+        let before = keys[0].slerp(&keys[1], 1.0 - h);
+        let after = keys[1].slerp(&keys[2], h);
+        let into = keys[1] * before.reversed();
+        let out = after * keys[1].reversed();
+        assert!(!into.approximately(out, 1e-7));
+    }
+
+    #[test]
+    fn log_exp_round_trip() {
+        let axis = Vector::new(-0.3, 1.0, 2.0).normalized();
+        for angle in [0.0, 1e-10, 1e-5, 0.5, 2.0, PI - 1e-6, PI - 1e-10] {
+            let rotor = Rotor::from_axis_angle(axis, angle);
+            let log = rotor.log();
+            assert!(log.approximately(Bivector::from_axis_vector(axis * angle), 1e-9));
+            assert!(log.exp().approximately(rotor, 1e-9));
+        }
+
+        // Just past a half turn the shorter way around is taken instead
+        let rotor = Rotor::from_axis_angle(axis, PI + 1e-6);
+        let expected = Bivector::from_axis_vector(axis * -(PI - 1e-6));
+        assert!(rotor.log().approximately(expected, 1e-9));
+        assert_same_rotation(&rotor.log().exp(), &rotor);
+
+        let half_turn = Rotor::from_axis_angle(axis, PI);
+        assert_same_rotation(&half_turn.log().exp(), &half_turn);
+        assert!(Rotor::identity()
+            .log()
+            .approximately(Bivector::zero(), EPSILON));
+    }
+
+    #[test]
+    fn log_near_identity_keeps_precision() {
+        let rotor = Rotor::from_axis_angle(Vector::unit_z(), 1e-12);
+        let log = rotor.log();
+        assert!((log.xy - 1e-12).abs() < 1e-24);
+        assert!(rotor.angle().approximately(1e-12, 1e-24));
+    }
+
+    #[test]
+    fn angle_and_plane() {
+        let axis = Vector::new(1.0, 1.0, 1.0).normalized();
+        for angle in [1e-8, 1.0, PI - 1e-8] {
+            let rotor = Rotor::from_axis_angle(axis, angle);
+            assert!(rotor.angle().approximately(angle, 1e-9));
+            assert!(rotor
+                .plane()
+                .approximately(Bivector::from_axis_vector(axis), 1e-6));
+
+            let rebuilt = Rotor::from_angle_and_plane(rotor.angle(), rotor.plane());
+            assert_same_rotation(&rebuilt, &rotor);
+        }
+
+        // The reverse way around past a half turn
+        let rotor = Rotor::from_axis_angle(axis, 1.5 * PI);
+        assert!(rotor.angle().approximately(0.5 * PI, 1e-9));
+        assert!(rotor
+            .plane()
+            .approximately(Bivector::from_axis_vector(-axis), 1e-9));
+
+        assert!(Rotor::identity().angle().approximately(0.0, EPSILON));
+        assert!(Rotor::identity()
+            .plane()
+            .approximately(Bivector::zero(), EPSILON));
+    }
+
+    #[test]
+    fn angle_between_rotors() {
+        let a = Rotor::from_axis_angle(Vector::unit_x(), 0.3);
+        let b = Rotor::from_axis_angle(Vector::unit_x(), 1.1);
+        assert!(a.angle_to(&b).approximately(0.8, 1e-9));
+        assert!(b.angle_to(&a).approximately(0.8, 1e-9));
+        assert!(a.angle_to(&a.scaled(-1.0)).approximately(0.0, 1e-9));
+
+        let c = Rotor::from_axis_angle(Vector::unit_y(), PI - 1e-9) * a;
+        assert!(a.angle_to(&c).approximately(PI - 1e-9, 1e-7));
+    }
+
+    #[test]
+    fn swing_twist_decomposition() {
+        let axis = Vector::new(0.0, 1.0, 1.0).normalized();
+        let rotors = [
+            Rotor::from_euler_angles(EulerOrder::XYZ, 0.3, -1.2, 2.0),
+            Rotor::from_axis_angle(axis, 1.0),
+            Rotor::from_axis_angle(Vector::unit_x(), 0.7),
+            Rotor::from_axis_angle(axis, PI - 1e-9),
+        ];
+
+        for rotor in rotors {
+            let (swing, twist) = rotor.swing_twist(axis);
+            assert_same_rotation(&(swing * twist), &rotor);
+
+            // The twist leaves the axis alone, the swing moves it along the shortest arc
+            assert!(axis.rotated_by(&twist).approximately(axis, 1e-9));
+            let swung = axis.rotated_by(&rotor);
+            let shortest = Rotor::from_rotation_between_vectors(axis, swung);
+            assert_same_rotation(&swing, &shortest);
+        }
+    }
+
+    #[test]
+    fn swing_twist_of_half_turn_swing() {
+        let rotor = Rotor::from_axis_angle(Vector::unit_x(), PI);
+        let (swing, twist) = rotor.swing_twist(Vector::unit_z());
+        assert!(twist.approximately(Rotor::identity(), EPSILON));
+        assert_same_rotation(&swing, &rotor);
+    }
+
     #[test]
     fn half_turn_rotation() {
         let mut v = test_vector();
@@ -345,6 +1103,22 @@ mod rotor_tests {
         assert!(v.approximately(Vector::new(0.0, 1.0, 0.0), EPSILON));
     }
 
+    #[test]
+    fn axis_vector_planes_are_right_handed() {
+        let quarter = PI / Float::from(2.0);
+        let cases = [
+            (Vector::unit_x(), Vector::unit_y(), Vector::unit_z()),
+            (Vector::unit_y(), Vector::unit_z(), Vector::unit_x()),
+            (Vector::unit_z(), Vector::unit_x(), Vector::unit_y()),
+        ];
+        for (axis, from, to) in cases {
+            let rotor = Rotor::from_angle_and_plane(quarter, Bivector::from_axis_vector(axis));
+            let mut v = from;
+            rotor.rotate_vector(&mut v);
+            assert!(v.approximately(to, 1e-12));
+        }
+    }
+
     #[test]
     fn vector_rotation_between_vectors() {
         let from = Vector::unit_x();
@@ -433,6 +1207,61 @@ mod rotor_tests {
         assert!((rotor * inverse).approximately(Rotor::default(), EPSILON));
     }
 
+    #[test]
+    fn axis_vector_is_right_handed() {
+        let quarter = PI / Float::from(2.0);
+        let about_x = Rotor::identity().advanced_by_axis(Vector::unit_x(), quarter);
+        let about_y = Rotor::identity().advanced_by_axis(Vector::unit_y(), quarter);
+        let about_z = Rotor::identity().advanced_by_axis(Vector::unit_z(), quarter);
+
+        assert!(Vector::unit_y()
+            .rotated_by(&about_x)
+            .approximately(Vector::unit_z(), EPSILON));
+        assert!(Vector::unit_z()
+            .rotated_by(&about_y)
+            .approximately(Vector::unit_x(), EPSILON));
+        assert!(Vector::unit_x()
+            .rotated_by(&about_z)
+            .approximately(Vector::unit_y(), EPSILON));
+    }
+
+    #[test]
+    fn advance_by_angular_velocity() {
+        // Two revolutions per unit time in the xy plane
+        let velocity = Bivector::new(Float::from(4.0) * PI, Float::ZERO, Float::ZERO);
+        let mut orientation = Rotor::identity();
+        let dt = Float::from(1.0 / 64.0);
+        for _ in 0..8 {
+            orientation.advance(velocity, dt);
+        }
+
+        let rotated = test_vector().rotated_by(&orientation);
+        assert!(rotated.approximately(Vector::new(0.0, 1.0, 0.0), Float::from(1e-12)));
+    }
+
+    #[test]
+    fn advance_stays_normalized() {
+        let velocity = Bivector::new(0.3, -1.2, 2.5);
+        let mut orientation = Rotor::identity();
+        for _ in 0..100_000 {
+            orientation.advance(velocity, Float::from(1e-3));
+        }
+        assert!(orientation.magnitude().approximately(Float::ONE, EPSILON));
+
+        let expected = Rotor::from_angular_velocity(velocity, Float::from(100.0));
+        let v = Vector::new(1.0, 2.0, 3.0);
+        assert!(v
+            .rotated_by(&orientation)
+            .approximately(v.rotated_by(&expected), Float::from(1e-9)));
+    }
+
+    #[test]
+    fn zero_angular_velocity() {
+        let orientation = Rotor::from_angle_and_plane(PI / Float::from(3.0), Bivector::unit_yz());
+        let advanced = orientation.advanced(Bivector::zero(), Float::ONE);
+        assert!(advanced.approximately(orientation, EPSILON));
+    }
+
     #[test]
     fn rotor_interaction_with_zero_vector() {
         let mut v = Vector::zero();
@@ -1 +1,3 @@
+pub use crate::aabb::Aabb;
 pub use crate::sphere::Sphere;
+pub use crate::triangle::Triangle;
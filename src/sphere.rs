@@ -4,18 +4,26 @@
 
 use std::ops::Deref;
 
+use serde::Deserialize;
+use serde::Serialize;
+
 use crate::circle::Circle;
 use crate::line::Line;
 use crate::traits::Distance;
+use crate::traits::FloatExt;
 use crate::Float;
 use crate::Intersects;
 use crate::Point;
 
+#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq)]
 pub struct Sphere {
     pub center: Point,
     pub radius: Float,
 }
 
+/// Relative slack allowed when testing whether a point lies on or inside a bounding sphere
+const BOUNDING_TOLERANCE: f64 = 1e-10;
+
 impl Sphere {
     pub fn new(center: Point, radius: Float) -> Self {
         Self { center, radius }
@@ -25,8 +33,207 @@ impl Sphere {
         self.center.distance_to_sq(point) < (self.radius * self.radius)
     }
 
-    pub fn minimum_bounding<P: Deref<Target = [Point]>>(_: P) -> Option<Self> {
-        todo!("Not yet implemented: Turns out this is quite non-trivial - implementations are welcome")
+    /// Computes the smallest [Sphere] enclosing every point in `points`
+    ///
+    /// Uses Welzl's algorithm in its iterative form, which runs in expected linear time.
+    /// Duplicate, collinear and coplanar points are handled. Returns `None` if `points`
+    /// is empty or contains non-finite coordinates
+    pub fn minimum_bounding<P: Deref<Target = [Point]>>(points: P) -> Option<Self> {
+        if points.is_empty() || !points.iter().all(is_finite) {
+            return None;
+        }
+
+        // The expected running time relies on visiting the points in random order
+        let mut points = points.to_vec();
+        shuffle(&mut points);
+
+        let mut sphere = Self::new(points[0], Float::ZERO);
+        for i in 1..points.len() {
+            if sphere.encloses(&points[i]) {
+                continue;
+            }
+
+            sphere = Self::new(points[i], Float::ZERO);
+            for j in 0..i {
+                if sphere.encloses(&points[j]) {
+                    continue;
+                }
+
+                sphere = Self::from_two(&points[i], &points[j]);
+                for k in 0..j {
+                    if sphere.encloses(&points[k]) {
+                        continue;
+                    }
+
+                    sphere = Self::from_three(&points[i], &points[j], &points[k]);
+                    for l in 0..k {
+                        if sphere.encloses(&points[l]) {
+                            continue;
+                        }
+
+                        sphere = Self::from_four(&points[i], &points[j], &points[k], &points[l]);
+                    }
+                }
+            }
+        }
+
+        Some(sphere)
+    }
+
+    /// Computes a [Sphere] enclosing every point in `points` using Ritter's algorithm
+    ///
+    /// Much cheaper than [Sphere::minimum_bounding], needing only a few passes over the
+    /// points, but the result is typically 5 to 20 percent larger than the minimum.
+    /// Returns `None` if `points` is empty or contains non-finite coordinates
+    pub fn approximate_bounding<P: Deref<Target = [Point]>>(points: P) -> Option<Self> {
+        if points.is_empty() || !points.iter().all(is_finite) {
+            return None;
+        }
+
+        let farthest_from = |from: &Point| {
+            points
+                .iter()
+                .max_by(|a, b| {
+                    let a = from.distance_to_sq(a);
+                    let b = from.distance_to_sq(b);
+                    a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .copied()
+                .unwrap_or(*from)
+        };
+
+        let x = farthest_from(&points[0]);
+        let y = farthest_from(&x);
+        let mut sphere = Self::from_two(&x, &y);
+
+        // Grow the sphere just enough to cover each point left outside of it
+        for point in points.iter() {
+            let d = sphere.center.distance_to(point);
+            if d > sphere.radius {
+                let radius = (sphere.radius + d) / Float::from(2.0);
+                let offset = (point - sphere.center) * ((radius - sphere.radius) / d);
+                sphere = Self::new(sphere.center + offset, radius);
+            }
+        }
+
+        Some(sphere)
+    }
+
+    /// Whether `point` lies within this sphere, allowing for a small amount of rounding error
+    fn encloses(&self, point: &Point) -> bool {
+        let slack = Float::ONE + Float::from(BOUNDING_TOLERANCE);
+        let radius = self.radius * slack + Float::from(BOUNDING_TOLERANCE);
+        self.center.distance_to_sq(point) <= radius * radius
+    }
+
+    /// The smallest sphere with `a` and `b` on its surface
+    fn from_two(a: &Point, b: &Point) -> Self {
+        let center = a + (b - a) / Float::from(2.0);
+        Self::new(center, center.distance_to(a))
+    }
+
+    /// The smallest sphere with `a`, `b` and `c` on its surface
+    ///
+    /// Collinear points have no circumcircle, in which case the sphere spanning the
+    /// two farthest apart points is used instead
+    fn from_three(a: &Point, b: &Point, c: &Point) -> Self {
+        let ab = b - a;
+        let ac = c - a;
+        let n = ab.cross(&ac);
+        let n_sq = n.length_sq();
+
+        if n_sq <= ab.length_sq() * ac.length_sq() * Float::from(BOUNDING_TOLERANCE) {
+            return Self::smallest_enclosing(
+                &[
+                    Self::from_two(a, b),
+                    Self::from_two(a, c),
+                    Self::from_two(b, c),
+                ],
+                &[*a, *b, *c],
+            );
+        }
+
+        let offset = (n.cross(&ab) * ac.length_sq() + ac.cross(&n) * ab.length_sq())
+            / (Float::from(2.0) * n_sq);
+        Self::new(a + offset, offset.length())
+    }
+
+    /// The smallest sphere with `a`, `b`, `c` and `d` on its surface
+    ///
+    /// Coplanar points have no unique circumsphere, in which case the smallest sphere
+    /// through any two or three of the points which encloses all four is used instead
+    fn from_four(a: &Point, b: &Point, c: &Point, d: &Point) -> Self {
+        let u = b - a;
+        let v = c - a;
+        let w = d - a;
+        let vw = v.cross(&w);
+        let det = u.dot(&vw);
+
+        let scale = u.length() * v.length() * w.length();
+        if det.abs() <= scale * Float::from(BOUNDING_TOLERANCE) {
+            let candidates = [
+                Self::from_two(a, b),
+                Self::from_two(a, c),
+                Self::from_two(a, d),
+                Self::from_two(b, c),
+                Self::from_two(b, d),
+                Self::from_two(c, d),
+                Self::from_three(a, b, c),
+                Self::from_three(a, b, d),
+                Self::from_three(a, c, d),
+                Self::from_three(b, c, d),
+            ];
+            return Self::smallest_enclosing(&candidates, &[*a, *b, *c, *d]);
+        }
+
+        let offset =
+            (vw * u.length_sq() + w.cross(&u) * v.length_sq() + u.cross(&v) * w.length_sq())
+                / (Float::from(2.0) * det);
+        Self::new(a + offset, offset.length())
+    }
+
+    /// The smallest of `candidates` which encloses all of `points`
+    fn smallest_enclosing(candidates: &[Self], points: &[Point]) -> Self {
+        candidates
+            .iter()
+            .filter(|sphere| points.iter().all(|p| sphere.encloses(p)))
+            .min_by(|a, b| {
+                a.radius
+                    .partial_cmp(&b.radius)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .copied()
+            .unwrap_or_else(|| {
+                // Only reachable through rounding error, the largest candidate is the safest choice
+                candidates
+                    .iter()
+                    .max_by(|a, b| {
+                        a.radius
+                            .partial_cmp(&b.radius)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .copied()
+                    .unwrap_or_default()
+            })
+    }
+}
+
+fn is_finite(point: &Point) -> bool {
+    point.x.is_finite() && point.y.is_finite() && point.z.is_finite()
+}
+
+/// Shuffles `points` in place with a small deterministic xorshift generator
+///
+/// Welzl's algorithm only needs an order which is unrelated to the structure of the input,
+/// so a fixed seed keeps results reproducible without pulling in a random number crate
+fn shuffle(points: &mut [Point]) {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15 ^ points.len() as u64;
+    for i in (1..points.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let j = (state % (i as u64 + 1)) as usize;
+        points.swap(i, j);
     }
 }
 
@@ -35,7 +242,6 @@ static MINIMUM_BOUNDING_SPHERE_EXPECTED_MESSAGE: &str =
 
 impl<P: Deref<Target = [Point]>> From<P> for Sphere {
     fn from(points: P) -> Self {
-        debug_assert!(points.len() >= 4);
         Self::minimum_bounding(points).expect(MINIMUM_BOUNDING_SPHERE_EXPECTED_MESSAGE)
     }
 }
@@ -101,3 +307,147 @@ impl Intersects for Sphere {
         }
     }
 }
+
+#[cfg(test)]
+mod sphere_tests {
+    use super::*;
+    use crate::traits::Approximately;
+
+    const EPSILON: Float = 1e-9;
+
+    /// A scattered but reproducible cloud of points
+    fn cloud(count: usize) -> Vec<Point> {
+        (0..count)
+            .map(|i| {
+                let t = Float::from(i as f64);
+                Point::new(
+                    (t * 1.7).sin() * 3.0 + 1.0,
+                    (t * 2.3).cos() * 2.0 - 4.0,
+                    (t * 0.9).sin() * (t * 0.4).cos() * 5.0,
+                )
+            })
+            .collect()
+    }
+
+    fn encloses_all(sphere: &Sphere, points: &[Point]) -> bool {
+        points
+            .iter()
+            .all(|p| sphere.center.distance_to(p) <= sphere.radius + EPSILON)
+    }
+
+    #[test]
+    fn empty_has_no_bounds() {
+        assert!(Sphere::minimum_bounding(Vec::new()).is_none());
+        assert!(Sphere::approximate_bounding(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn non_finite_has_no_bounds() {
+        let points = vec![Point::origin(), Point::new(Float::NAN, 0.0, 0.0)];
+        assert!(Sphere::minimum_bounding(points.clone()).is_none());
+        assert!(Sphere::approximate_bounding(points).is_none());
+    }
+
+    #[test]
+    fn single_point() {
+        let sphere = Sphere::minimum_bounding(vec![Point::new(1.0, 2.0, 3.0)]).unwrap();
+        assert!(sphere
+            .center
+            .approximately(&Point::new(1.0, 2.0, 3.0), EPSILON));
+        assert!(sphere.radius.approximately(0.0, EPSILON));
+    }
+
+    #[test]
+    fn duplicate_points() {
+        let points = vec![Point::new(1.0, 1.0, 1.0); 10];
+        let sphere = Sphere::minimum_bounding(points).unwrap();
+        assert!(sphere
+            .center
+            .approximately(&Point::new(1.0, 1.0, 1.0), EPSILON));
+        assert!(sphere.radius.approximately(0.0, EPSILON));
+    }
+
+    #[test]
+    fn collinear_points() {
+        let points: Vec<Point> = (0..=10)
+            .map(|i| Point::new(Float::from(i as f64), 0.0, 0.0))
+            .collect();
+        let sphere = Sphere::minimum_bounding(points).unwrap();
+        assert!(sphere
+            .center
+            .approximately(&Point::new(5.0, 0.0, 0.0), EPSILON));
+        assert!(sphere.radius.approximately(5.0, EPSILON));
+    }
+
+    #[test]
+    fn coplanar_points() {
+        let mut points = vec![
+            Point::new(-1.0, -1.0, 0.0),
+            Point::new(1.0, -1.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(-1.0, 1.0, 0.0),
+        ];
+        points.extend(
+            cloud(20)
+                .iter()
+                .map(|p| Point::new(p.x / 10.0, p.y / 10.0, 0.0)),
+        );
+        let sphere = Sphere::minimum_bounding(points).unwrap();
+        assert!(sphere.center.approximately(&Point::origin(), EPSILON));
+        assert!(sphere.radius.approximately(Float::sqrt(2.0), EPSILON));
+    }
+
+    #[test]
+    fn cube_corners() {
+        let mut points = Vec::new();
+        for x in [-1.0, 1.0] {
+            for y in [-1.0, 1.0] {
+                for z in [-1.0, 1.0] {
+                    points.push(Point::new(x, y, z));
+                }
+            }
+        }
+        let sphere = Sphere::from(&points[..]);
+        assert!(sphere.center.approximately(&Point::origin(), EPSILON));
+        assert!(sphere.radius.approximately(Float::sqrt(3.0), EPSILON));
+    }
+
+    #[test]
+    fn tetrahedron_with_interior_points() {
+        let mut points = vec![
+            Point::new(1.0, 1.0, 1.0),
+            Point::new(1.0, -1.0, -1.0),
+            Point::new(-1.0, 1.0, -1.0),
+            Point::new(-1.0, -1.0, 1.0),
+        ];
+        points.extend(
+            cloud(50)
+                .iter()
+                .map(|p| Point::new(p.x / 20.0, p.y / 20.0, p.z / 20.0)),
+        );
+        let sphere = Sphere::from(points);
+        assert!(sphere.center.approximately(&Point::origin(), EPSILON));
+        assert!(sphere.radius.approximately(Float::sqrt(3.0), EPSILON));
+    }
+
+    #[test]
+    fn minimum_encloses_cloud() {
+        let points = cloud(1000);
+        let sphere = Sphere::minimum_bounding(&points[..]).unwrap();
+        assert!(encloses_all(&sphere, &points));
+
+        // The minimum sphere is supported by points on its surface, so it can not shrink
+        let shrunk = Sphere::new(sphere.center, sphere.radius - 1e-6);
+        assert!(!encloses_all(&shrunk, &points));
+    }
+
+    #[test]
+    fn approximate_is_no_smaller_than_minimum() {
+        let points = cloud(1000);
+        let minimum = Sphere::minimum_bounding(&points[..]).unwrap();
+        let approximate = Sphere::approximate_bounding(&points[..]).unwrap();
+        assert!(encloses_all(&approximate, &points));
+        assert!(approximate.radius >= minimum.radius - EPSILON);
+        assert!(approximate.radius <= minimum.radius * 1.25);
+    }
+}
@@ -0,0 +1,550 @@
+//!
+//! Affine transforms and transform hierarchies
+//!
+//! A [Transform] scales, then rotates, then translates. A [Hierarchy] arranges transforms
+//! into a tree where each node is placed relative to its parent, caching the world [Matrix]
+//! of every node and only recomputing those whose ancestors changed
+//!
+//! Matrices transform column vectors with the translation in column 3, like
+//! [Matrix::from_translation], so they work directly with `&matrix * &point` and
+//! [crate::aabb::Aabb::transformed]. A child's world matrix is the world matrix of its parent
+//! times its local matrix, `parent * local`
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::isometry::Isometry;
+use crate::matrix::Matrix;
+use crate::rotor::Rotor;
+use crate::traits::FloatExt;
+use crate::traits::Zero;
+use crate::Approximately;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+use std::ops::Mul;
+
+/// A scale followed by a rotation and then a translation
+///
+/// The product `a * b` is the transform that applies `b` first and then `a`. Non-uniform
+/// scales followed by rotations can shear, which a [Transform] can not represent, so products
+/// and inverses are only exact when the scale of the outer transform is uniform. The
+/// [Matrix] of the result is always exact, see [Transform::to_matrix]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Transform {
+    pub translation: Vector,
+    pub rotation: Rotor,
+    pub scale: Vector,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform {
+    pub fn new(translation: Vector, rotation: Rotor, scale: Vector) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(
+            Vector::zero(),
+            Rotor::identity(),
+            Vector::new(Float::ONE, Float::ONE, Float::ONE),
+        )
+    }
+
+    /// Constructs a new [Transform] that only translates
+    pub fn from_translation(translation: Vector) -> Self {
+        Self {
+            translation,
+            ..Self::identity()
+        }
+    }
+
+    /// Constructs a new [Transform] that only rotates
+    pub fn from_rotation(rotation: Rotor) -> Self {
+        Self {
+            rotation,
+            ..Self::identity()
+        }
+    }
+
+    /// Constructs a new [Transform] that only scales
+    pub fn from_scale(scale: Vector) -> Self {
+        Self {
+            scale,
+            ..Self::identity()
+        }
+    }
+
+    /// Constructs a new [Transform] from an affine [Matrix] laid out as by
+    /// [Transform::to_matrix], see [Matrix::decompose_column_vector]
+    ///
+    /// Returns `None` if the matrix shears or can not be decomposed
+    pub fn from_matrix(matrix: &Matrix) -> Option<Self> {
+        matrix
+            .decompose_column_vector()
+            .map(|d| Self::new(d.translation, d.orientation, d.scale))
+    }
+
+    /// Applies this [Transform] to a [Point]
+    pub fn transform_point(&self, point: &Point) -> Point {
+        Point::from(self.transform_vector(&point.as_vector()) + self.translation)
+    }
+
+    /// Applies the scale and rotation of this [Transform] to a [Vector], ignoring its translation
+    pub fn transform_vector(&self, vector: &Vector) -> Vector {
+        (vector * self.scale).rotated_by(&self.rotation)
+    }
+
+    /// Returns the inverse of this [Transform]
+    ///
+    /// Exact when the scale is uniform, otherwise the inverse of [Transform::to_matrix] should
+    /// be used instead
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.reversed();
+        let scale = Vector::new(Float::ONE, Float::ONE, Float::ONE) / self.scale;
+        let translation = -(self.translation.rotated_by(&rotation) * scale);
+        Self::new(translation, rotation, scale)
+    }
+
+    /// Inverts this [Transform] in place, see [Transform::inverse]
+    pub fn invert(&mut self) {
+        *self = self.inverse();
+    }
+
+    /// Interpolates from this [Transform] to `to` by the amount of `weight`
+    ///
+    /// The translation and scale are interpolated linearly, and the rotation with [Rotor::slerp]
+    pub fn interpolate<F: Into<Float>>(&self, to: &Self, weight: F) -> Self {
+        let w = weight.into();
+        Self::new(
+            self.translation.lerp(&to.translation, w),
+            self.rotation.slerp(&to.rotation, w),
+            self.scale.lerp(&to.scale, w),
+        )
+    }
+
+    /// Returns the [Matrix] of this [Transform], which transforms column vectors with the
+    /// translation in column 3 like [Matrix::from_translation]
+    ///
+    /// `&transform.to_matrix() * &point` is the same as [Transform::transform_point], and the
+    /// product `a.to_matrix() * b.to_matrix()` applies `b` first and then `a`
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_translation_orientation_and_scale(self.translation, self.rotation, self.scale)
+            .transposed()
+    }
+}
+
+impl Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Self::Output {
+        Transform::new(
+            self.transform_vector(&rhs.translation) + self.translation,
+            self.rotation * rhs.rotation,
+            self.scale * rhs.scale,
+        )
+    }
+}
+
+impl From<Isometry> for Transform {
+    fn from(isometry: Isometry) -> Self {
+        Self {
+            translation: isometry.translation,
+            rotation: isometry.rotation,
+            ..Self::identity()
+        }
+    }
+}
+
+impl From<Transform> for Matrix {
+    fn from(transform: Transform) -> Self {
+        transform.to_matrix()
+    }
+}
+
+impl Approximately for Transform {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        self.translation.approximately(other.translation, epsilon)
+            && self.rotation.approximately(other.rotation, epsilon)
+            && self.scale.approximately(other.scale, epsilon)
+    }
+}
+
+/// A handle to a node of a [Hierarchy]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Clone)]
+struct Node {
+    local: Transform,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    world: Matrix,
+    dirty: bool,
+}
+
+/// A tree of [Transform]s, each placed relative to its parent
+///
+/// World matrices are cached. Changing a node marks it and its descendants dirty, and they
+/// are recomputed the next time they are asked for or when [Hierarchy::update] is called
+#[derive(Debug, Clone, Default)]
+pub struct Hierarchy {
+    nodes: Vec<Node>,
+}
+
+impl Hierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of nodes in this [Hierarchy]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Adds a node with the `local` transform under `parent`, or as a root if `parent` is `None`
+    pub fn insert(&mut self, local: Transform, parent: Option<NodeId>) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            local,
+            parent,
+            children: Vec::new(),
+            world: Matrix::identity(),
+            dirty: true,
+        });
+
+        if let Some(parent) = parent {
+            self.nodes[parent.0].children.push(id);
+        }
+        id
+    }
+
+    /// The transform of a node relative to its parent
+    pub fn local(&self, id: NodeId) -> &Transform {
+        &self.nodes[id.0].local
+    }
+
+    /// Replaces the transform of a node relative to its parent
+    pub fn set_local(&mut self, id: NodeId, local: Transform) {
+        self.nodes[id.0].local = local;
+        self.mark_dirty(id);
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.nodes[id.0].children
+    }
+
+    /// Moves a node and its descendants under a new `parent`, keeping its local transform
+    ///
+    /// Returns `false` and leaves the hierarchy unchanged if `parent` is the node itself or
+    /// one of its descendants
+    pub fn set_parent(&mut self, id: NodeId, parent: Option<NodeId>) -> bool {
+        let mut ancestor = parent;
+        while let Some(node) = ancestor {
+            if node == id {
+                return false;
+            }
+            ancestor = self.nodes[node.0].parent;
+        }
+
+        if let Some(old) = self.nodes[id.0].parent {
+            self.nodes[old.0].children.retain(|child| *child != id);
+        }
+        if let Some(new) = parent {
+            self.nodes[new.0].children.push(id);
+        }
+        self.nodes[id.0].parent = parent;
+        self.mark_dirty(id);
+        true
+    }
+
+    /// Returns the world [Matrix] of a node, recomputing it and its ancestors if they are dirty
+    pub fn world_matrix(&mut self, id: NodeId) -> Matrix {
+        if self.nodes[id.0].dirty {
+            let local = self.nodes[id.0].local.to_matrix();
+            let world = match self.nodes[id.0].parent {
+                Some(parent) => self.world_matrix(parent) * local,
+                None => local,
+            };
+
+            let node = &mut self.nodes[id.0];
+            node.world = world;
+            node.dirty = false;
+        }
+        self.nodes[id.0].world.clone()
+    }
+
+    /// Returns the cached world [Matrix] of a node, or `None` if it is dirty
+    pub fn cached_world_matrix(&self, id: NodeId) -> Option<Matrix> {
+        let node = &self.nodes[id.0];
+        (!node.dirty).then(|| node.world.clone())
+    }
+
+    /// Recomputes the world matrices of every dirty node
+    pub fn update(&mut self) {
+        for id in 0..self.nodes.len() {
+            self.world_matrix(NodeId(id));
+        }
+    }
+
+    /// Marks a node and all of its descendants as dirty
+    fn mark_dirty(&mut self, id: NodeId) {
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let node = &mut self.nodes[id.0];
+            node.dirty = true;
+            stack.extend_from_slice(&node.children);
+        }
+    }
+}
+
+#[cfg(test)]
+mod transform_tests {
+    use super::*;
+    use crate::aabb::Aabb;
+    use crate::constant::PI;
+
+    const EPSILON: Float = 1e-9;
+
+    fn transform() -> Transform {
+        Transform::new(
+            Vector::new(1.0, -2.0, 3.0),
+            Rotor::from_axis_angle(Vector::new(1.0, 1.0, 0.0), 0.8),
+            Vector::new(2.0, 0.5, 3.0),
+        )
+    }
+
+    #[test]
+    fn point_transform_matches_matrix() {
+        let transform = transform();
+        let p = Point::new(0.5, 1.0, -1.5);
+        let expected = &transform.to_matrix() * &p;
+        assert!(transform
+            .transform_point(&p)
+            .approximately(&expected, EPSILON));
+
+        let scaled = Transform::from_scale(Vector::new(2.0, 3.0, 4.0));
+        let p = scaled.transform_point(&Point::new(1.0, 1.0, 1.0));
+        assert!(p.approximately(&Point::new(2.0, 3.0, 4.0), EPSILON));
+    }
+
+    #[test]
+    fn composition() {
+        let a = Transform::new(
+            Vector::new(0.0, 1.0, 0.0),
+            Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0),
+            Vector::new(2.0, 2.0, 2.0),
+        );
+        let b = transform();
+        let p = Point::new(1.0, 2.0, 3.0);
+        let sequential = a.transform_point(&b.transform_point(&p));
+        assert!((a * b)
+            .transform_point(&p)
+            .approximately(&sequential, EPSILON));
+
+        // The matrix product is exact even when the outer scale is non-uniform
+        let matrix = a.to_matrix() * b.to_matrix();
+        assert!((&matrix * &p).approximately(&sequential, EPSILON));
+    }
+
+    #[test]
+    fn inverse() {
+        let uniform = Transform::new(
+            Vector::new(1.0, -2.0, 3.0),
+            Rotor::from_axis_angle(Vector::new(1.0, 1.0, 0.0), 0.8),
+            Vector::new(0.5, 0.5, 0.5),
+        );
+        let p = Point::new(0.5, 1.0, -1.5);
+        let back = uniform
+            .inverse()
+            .transform_point(&uniform.transform_point(&p));
+        assert!(back.approximately(&p, EPSILON));
+        assert!((uniform * uniform.inverse()).approximately(Transform::identity(), EPSILON));
+    }
+
+    #[test]
+    fn interpolation() {
+        let a = Transform::identity();
+        let b = Transform::new(
+            Vector::new(2.0, 0.0, 0.0),
+            Rotor::from_axis_angle(Vector::unit_y(), 1.0),
+            Vector::new(3.0, 1.0, 1.0),
+        );
+        let halfway = a.interpolate(&b, 0.5);
+        assert!(halfway
+            .translation
+            .approximately(Vector::new(1.0, 0.0, 0.0), EPSILON));
+        assert!(halfway
+            .scale
+            .approximately(Vector::new(2.0, 1.0, 1.0), EPSILON));
+        let rotation = Rotor::from_axis_angle(Vector::unit_y(), 0.5);
+        assert!(halfway.rotation.approximately(rotation, EPSILON));
+        assert!(a.interpolate(&b, 1.0).approximately(b, EPSILON));
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        let transform = transform();
+        let without_translation = Transform {
+            translation: Vector::zero(),
+            ..transform
+        };
+        for transform in [transform, without_translation] {
+            let recovered = Transform::from_matrix(&transform.to_matrix()).unwrap();
+            assert!(recovered
+                .translation
+                .approximately(transform.translation, EPSILON));
+            assert!(recovered.scale.approximately(transform.scale, EPSILON));
+
+            // The rotor is recovered up to sign, which describes the same rotation
+            assert!(recovered
+                .rotation
+                .canonicalized()
+                .approximately(transform.rotation.canonicalized(), EPSILON));
+        }
+    }
+
+    #[test]
+    fn hierarchy_world_matrices() {
+        let mut hierarchy = Hierarchy::new();
+        let root = hierarchy.insert(
+            Transform::from_translation(Vector::new(10.0, 0.0, 0.0)),
+            None,
+        );
+        let arm = hierarchy.insert(
+            Transform::new(
+                Vector::new(0.0, 1.0, 0.0),
+                Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0),
+                Vector::new(2.0, 2.0, 2.0),
+            ),
+            Some(root),
+        );
+        let hand = hierarchy.insert(
+            Transform::from_translation(Vector::new(1.0, 0.0, 0.0)),
+            Some(arm),
+        );
+        assert_eq!(hierarchy.children(root), &[arm]);
+        assert_eq!(hierarchy.parent(hand), Some(arm));
+
+        // The hand sits one unit along the arm's rotated and scaled x axis
+        let world = hierarchy.world_matrix(hand);
+        let position = &world * &Point::origin();
+        assert!(position.approximately(&Point::new(10.0, 3.0, 0.0), EPSILON));
+
+        let composed = *hierarchy.local(root) * *hierarchy.local(arm) * *hierarchy.local(hand);
+        let expected = composed.transform_point(&Point::new(1.0, 1.0, 1.0));
+        assert!((&world * &Point::new(1.0, 1.0, 1.0)).approximately(&expected, EPSILON));
+    }
+
+    #[test]
+    fn hierarchy_world_matrix_transforms_bounds() {
+        let mut hierarchy = Hierarchy::new();
+        let root = hierarchy.insert(
+            Transform::from_translation(Vector::new(10.0, 0.0, 0.0)),
+            None,
+        );
+        let child = hierarchy.insert(
+            Transform::new(
+                Vector::new(0.0, 0.0, 2.0),
+                Rotor::from_axis_angle(Vector::unit_z(), PI / 2.0),
+                Vector::new(2.0, 1.0, 1.0),
+            ),
+            Some(root),
+        );
+
+        let world = hierarchy.world_matrix(root);
+        let bounds = Aabb::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0));
+        let moved = bounds.transformed(&world);
+        assert!(moved
+            .min
+            .approximately(&Point::new(9.0, -1.0, -1.0), EPSILON));
+        assert!(moved
+            .max
+            .approximately(&Point::new(11.0, 1.0, 1.0), EPSILON));
+
+        // The child stretches along x, turns that onto y, lifts along z and follows the root
+        let world = hierarchy.world_matrix(child);
+        let p = Point::new(1.0, 0.0, 0.0);
+        assert!((&world * &p).approximately(&Point::new(10.0, 2.0, 2.0), EPSILON));
+        let moved = bounds.transformed(&world);
+        assert!(moved
+            .min
+            .approximately(&Point::new(9.0, -2.0, 1.0), EPSILON));
+        assert!(moved
+            .max
+            .approximately(&Point::new(11.0, 2.0, 3.0), EPSILON));
+    }
+
+    #[test]
+    fn hierarchy_dirty_propagation() {
+        let mut hierarchy = Hierarchy::new();
+        let root = hierarchy.insert(Transform::identity(), None);
+        let child = hierarchy.insert(
+            Transform::from_translation(Vector::new(1.0, 0.0, 0.0)),
+            Some(root),
+        );
+        let other = hierarchy.insert(Transform::identity(), None);
+
+        hierarchy.update();
+        assert!(hierarchy.cached_world_matrix(child).is_some());
+
+        hierarchy.set_local(
+            root,
+            Transform::from_translation(Vector::new(0.0, 5.0, 0.0)),
+        );
+        assert!(hierarchy.cached_world_matrix(root).is_none());
+        assert!(hierarchy.cached_world_matrix(child).is_none());
+        assert!(hierarchy.cached_world_matrix(other).is_some());
+
+        let position = &hierarchy.world_matrix(child) * &Point::origin();
+        assert!(position.approximately(&Point::new(1.0, 5.0, 0.0), EPSILON));
+        assert!(hierarchy.cached_world_matrix(root).is_some());
+    }
+
+    #[test]
+    fn hierarchy_reparenting() {
+        let mut hierarchy = Hierarchy::new();
+        let a = hierarchy.insert(
+            Transform::from_translation(Vector::new(1.0, 0.0, 0.0)),
+            None,
+        );
+        let b = hierarchy.insert(
+            Transform::from_translation(Vector::new(0.0, 1.0, 0.0)),
+            Some(a),
+        );
+        let c = hierarchy.insert(
+            Transform::from_translation(Vector::new(0.0, 0.0, 1.0)),
+            None,
+        );
+
+        assert!(!hierarchy.set_parent(a, Some(b)));
+        assert!(!hierarchy.set_parent(a, Some(a)));
+
+        hierarchy.update();
+        assert!(hierarchy.set_parent(b, Some(c)));
+        assert!(hierarchy.children(a).is_empty());
+        assert_eq!(hierarchy.children(c), &[b]);
+
+        let position = &hierarchy.world_matrix(b) * &Point::origin();
+        assert!(position.approximately(&Point::new(0.0, 1.0, 1.0), EPSILON));
+        assert_eq!(hierarchy.len(), 3);
+    }
+}
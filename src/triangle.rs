@@ -0,0 +1,655 @@
+//!
+//! Triangles in 3D space
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::plane::Plane;
+use crate::ray::Ray;
+use crate::ray::RayHit;
+use crate::segment::LineSegment;
+use crate::traits::Approximately;
+use crate::traits::Distance;
+use crate::traits::FloatExt;
+use crate::traits::Intersects;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+
+/// Tolerance used by the overlap tests, relative to the size of the triangles involved
+const TOLERANCE: f64 = 1e-10;
+
+/// A triangle in 3D space, wound counter-clockwise from `a` to `b` to `c` about its normal
+#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq)]
+pub struct Triangle {
+    pub a: Point,
+    pub b: Point,
+    pub c: Point,
+}
+
+impl Triangle {
+    pub fn new(a: Point, b: Point, c: Point) -> Self {
+        Self { a, b, c }
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> [Point; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    /// The cross product of the edges `ab` and `ac`, with a length of twice the area
+    #[inline]
+    fn scaled_normal(&self) -> Vector {
+        (self.b - self.a).cross(&(self.c - self.a))
+    }
+
+    /// The unit normal, following the right-hand rule over the winding `a`, `b`, `c`
+    #[inline]
+    pub fn normal(&self) -> Vector {
+        self.scaled_normal().normalized()
+    }
+
+    #[inline]
+    pub fn area(&self) -> Float {
+        self.scaled_normal().length() / Float::from(2.0)
+    }
+
+    #[inline]
+    pub fn centroid(&self) -> Point {
+        let sum = self.a.as_vector() + self.b.as_vector() + self.c.as_vector();
+        Point::from(sum / Float::from(3.0))
+    }
+
+    /// Test whether the triangle has no area, its vertices being collinear or coincident
+    pub fn is_degenerate(&self) -> bool {
+        self.scaled_normal().length_sq() == Float::ZERO
+    }
+
+    /// The plane the triangle lies in
+    pub fn plane(&self) -> Plane {
+        Plane::from(self)
+    }
+
+    /// Computes the barycentric coordinates `(u, v, w)` of `point` with respect to the vertices
+    /// `a`, `b` and `c`, such that `point = a * u + b * v + c * w` and `u + v + w = 1`
+    ///
+    /// Points off the plane of the triangle are projected onto it. Returns `None` for a
+    /// degenerate triangle
+    pub fn barycentric(&self, point: &Point) -> Option<(Float, Float, Float)> {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let ap = point - self.a;
+
+        let d00 = ab.dot(&ab);
+        let d01 = ab.dot(&ac);
+        let d11 = ac.dot(&ac);
+        let d20 = ap.dot(&ab);
+        let d21 = ap.dot(&ac);
+
+        let denom = d00 * d11 - d01 * d01;
+        if denom == Float::ZERO {
+            return None;
+        }
+
+        let v = (d11 * d20 - d01 * d21) / denom;
+        let w = (d00 * d21 - d01 * d20) / denom;
+        Some((Float::ONE - v - w, v, w))
+    }
+
+    /// Returns the point with barycentric coordinates `(u, v, w)`, see [Triangle::barycentric]
+    pub fn from_barycentric(&self, u: Float, v: Float, w: Float) -> Point {
+        Point::from(self.a.as_vector() * u + self.b.as_vector() * v + self.c.as_vector() * w)
+    }
+
+    /// Test whether `point` lies inside or on the edge of the triangle, within `epsilon`
+    /// of its plane
+    pub fn contains(&self, point: &Point, epsilon: Float) -> bool {
+        self.closest_point(point).distance_to_sq(point) <= epsilon * epsilon
+    }
+
+    /// Returns the point on the triangle which is closest to `point`
+    pub fn closest_point(&self, point: &Point) -> Point {
+        let (a, b, c, p) = (self.a, self.b, self.c, *point);
+        let ab = b - a;
+        let ac = c - a;
+
+        // Walk through the Voronoi regions of the vertices and edges, see
+        // Ericson, Real-Time Collision Detection, 5.1.5
+        let ap = p - a;
+        let d1 = ab.dot(&ap);
+        let d2 = ac.dot(&ap);
+        if d1 <= Float::ZERO && d2 <= Float::ZERO {
+            return a;
+        }
+
+        let bp = p - b;
+        let d3 = ab.dot(&bp);
+        let d4 = ac.dot(&bp);
+        if d3 >= Float::ZERO && d4 <= d3 {
+            return b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= Float::ZERO && d1 >= Float::ZERO && d3 <= Float::ZERO {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = p - c;
+        let d5 = ab.dot(&cp);
+        let d6 = ac.dot(&cp);
+        if d6 >= Float::ZERO && d5 <= d6 {
+            return c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= Float::ZERO && d2 >= Float::ZERO && d6 <= Float::ZERO {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= Float::ZERO && (d4 - d3) >= Float::ZERO && (d5 - d6) >= Float::ZERO {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        // Inside the face
+        let denom = Float::ONE / (va + vb + vc);
+        a + ab * (vb * denom) + ac * (vc * denom)
+    }
+
+    /// The length of the longest edge, used to scale tolerances
+    fn scale(&self) -> Float {
+        let ab = (self.b - self.a).length_sq();
+        let bc = (self.c - self.b).length_sq();
+        let ca = (self.a - self.c).length_sq();
+        Float::max(ab, Float::max(bc, ca)).sqrt()
+    }
+
+    /// The segment a triangle with negligible area collapses onto, which is its longest edge,
+    /// or `None` if its area is not negligible at `tolerance`
+    fn collapsed(&self, tolerance: Float) -> Option<LineSegment> {
+        if self.scaled_normal().length() > tolerance * self.scale() {
+            return None;
+        }
+
+        let edges = [(self.a, self.b), (self.b, self.c), (self.c, self.a)];
+        edges
+            .into_iter()
+            .map(|(start, end)| LineSegment::new(start, end))
+            .max_by(|a, b| {
+                a.length()
+                    .partial_cmp(&b.length())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+
+    /// The point where one of two triangles crosses the other after collapsing onto a segment,
+    /// or `None` if neither has collapsed. Two collapsed triangles are never considered to cross
+    fn collapsed_crossing(&self, other: &Triangle, tolerance: Float) -> Option<Option<Point>> {
+        let pierce = |triangle: &Triangle, segment: LineSegment| {
+            let ray = Ray::new(segment.start, segment.end - segment.start).with_range(0.0, 1.0);
+            triangle.intersection(&ray).map(|hit| hit.point)
+        };
+
+        match (self.collapsed(tolerance), other.collapsed(tolerance)) {
+            (None, None) => None,
+            (Some(segment), None) => Some(pierce(other, segment)),
+            (None, Some(segment)) => Some(pierce(self, segment)),
+            (Some(_), Some(_)) => Some(None),
+        }
+    }
+
+    /// Signed distances of each vertex to the plane through `other`, scaled by the
+    /// length of its normal, with values within `tolerance` snapped to zero
+    fn plane_distances(&self, other: &Triangle, tolerance: Float) -> [Float; 3] {
+        let n = other.normal();
+        self.vertices().map(|v| {
+            let d = n.dot(&(v - other.a));
+            if d.abs() <= tolerance {
+                Float::ZERO
+            } else {
+                d
+            }
+        })
+    }
+
+    /// The segment where this triangle crosses the plane it has `distances` from,
+    /// as a pair of points along `direction`
+    fn plane_crossing(&self, distances: &[Float; 3], direction: &Vector) -> (Point, Point) {
+        let vertices = self.vertices();
+        let mut crossing: Vec<Point> = Vec::with_capacity(3);
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+            let (di, dj) = (distances[i], distances[j]);
+            if di == Float::ZERO {
+                crossing.push(vertices[i]);
+            } else if (di < Float::ZERO && dj > Float::ZERO)
+                || (di > Float::ZERO && dj < Float::ZERO)
+            {
+                let t = di / (di - dj);
+                crossing.push(vertices[i] + (vertices[j] - vertices[i]) * t);
+            }
+        }
+
+        let along = |p: &&Point| direction.dot(&p.as_vector());
+        let order = |a: &&Point, b: &&Point| {
+            along(a)
+                .partial_cmp(&along(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        };
+        let start = crossing.iter().min_by(order).copied().unwrap_or(self.a);
+        let end = crossing.iter().max_by(order).copied().unwrap_or(self.a);
+        (start, end)
+    }
+
+    /// Overlap test for two triangles lying in the same plane
+    fn coplanar_overlap(&self, other: &Triangle) -> bool {
+        // Drop the dominant axis of the normal to work in 2D
+        let n = self.scaled_normal();
+        let (x, y, z) = (n.x.abs(), n.y.abs(), n.z.abs());
+        let project = |p: &Point| -> (Float, Float) {
+            if x >= y && x >= z {
+                (p.y, p.z)
+            } else if y >= z {
+                (p.x, p.z)
+            } else {
+                (p.x, p.y)
+            }
+        };
+
+        let first = self.vertices().map(|v| project(&v));
+        let second = other.vertices().map(|v| project(&v));
+
+        let orient = |a: (Float, Float), b: (Float, Float), c: (Float, Float)| {
+            (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+        };
+
+        let inside = |tri: &[(Float, Float); 3], p: (Float, Float)| {
+            let d1 = orient(tri[0], tri[1], p);
+            let d2 = orient(tri[1], tri[2], p);
+            let d3 = orient(tri[2], tri[0], p);
+            let negative = d1 < Float::ZERO || d2 < Float::ZERO || d3 < Float::ZERO;
+            let positive = d1 > Float::ZERO || d2 > Float::ZERO || d3 > Float::ZERO;
+            !(negative && positive)
+        };
+
+        let edges_cross =
+            |p1: (Float, Float), p2: (Float, Float), q1: (Float, Float), q2: (Float, Float)| {
+                let d1 = orient(q1, q2, p1);
+                let d2 = orient(q1, q2, p2);
+                let d3 = orient(p1, p2, q1);
+                let d4 = orient(p1, p2, q2);
+                ((d1 > Float::ZERO && d2 < Float::ZERO) || (d1 < Float::ZERO && d2 > Float::ZERO))
+                    && ((d3 > Float::ZERO && d4 < Float::ZERO)
+                        || (d3 < Float::ZERO && d4 > Float::ZERO))
+            };
+
+        for i in 0..3 {
+            for j in 0..3 {
+                if edges_cross(first[i], first[(i + 1) % 3], second[j], second[(j + 1) % 3]) {
+                    return true;
+                }
+            }
+        }
+
+        first.iter().any(|p| inside(&second, *p)) || second.iter().any(|p| inside(&first, *p))
+    }
+}
+
+impl From<&Triangle> for Plane {
+    fn from(triangle: &Triangle) -> Self {
+        Plane::from(&[&triangle.a, &triangle.b, &triangle.c])
+    }
+}
+
+impl From<Triangle> for Plane {
+    fn from(triangle: Triangle) -> Self {
+        Plane::from(&triangle)
+    }
+}
+
+impl Approximately for Triangle {
+    fn approximately(&self, other: Self, epsilon: Float) -> bool {
+        self.a.approximately(&other.a, epsilon)
+            && self.b.approximately(&other.b, epsilon)
+            && self.c.approximately(&other.c, epsilon)
+    }
+}
+
+impl Distance<Point> for Triangle {
+    fn distance_to_sq(&self, other: &Point) -> Float {
+        self.closest_point(other).distance_to_sq(other)
+    }
+}
+
+impl Intersects<Ray> for Triangle {
+    type Intersection = Option<RayHit>;
+
+    fn interesects(&self, other: &Ray) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Casts the ray against both sides of the triangle using the Möller–Trumbore algorithm
+    fn intersection(&self, other: &Ray) -> Self::Intersection {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let p = other.direction.cross(&ac);
+        let det = ab.dot(&p);
+        if det == Float::ZERO {
+            return None;
+        }
+
+        let inv_det = Float::ONE / det;
+        let s = other.origin - self.a;
+        let u = s.dot(&p) * inv_det;
+        if !(Float::ZERO..=Float::ONE).contains(&u) {
+            return None;
+        }
+
+        let q = s.cross(&ab);
+        let v = other.direction.dot(&q) * inv_det;
+        if v < Float::ZERO || u + v > Float::ONE {
+            return None;
+        }
+
+        let t = ac.dot(&q) * inv_det;
+        other
+            .in_range(t)
+            .then(|| other.facing_hit(t, ab.cross(&ac)))
+    }
+}
+
+impl Intersects for Triangle {
+    /// The segment along which two triangles cross
+    ///
+    /// Overlapping coplanar triangles share an area rather than a segment, so although
+    /// they do intersect, `None` is returned for them
+    ///
+    /// A triangle with negligible area has no plane to cross, and is tested as the segment it
+    /// collapses onto instead. Where it pierces the other triangle the segment has zero length,
+    /// and a collapsed triangle lying in the plane of the other is not considered to cross it
+    type Intersection = Option<LineSegment>;
+
+    fn interesects(&self, other: &Self) -> bool {
+        let tolerance = Float::from(TOLERANCE) * Float::max(self.scale(), other.scale());
+        if let Some(crossing) = self.collapsed_crossing(other, tolerance) {
+            return crossing.is_some();
+        }
+
+        let first = self.plane_distances(other, tolerance);
+        if first.iter().all(|d| *d > Float::ZERO) || first.iter().all(|d| *d < Float::ZERO) {
+            return false;
+        }
+
+        if first.iter().all(|d| *d == Float::ZERO) {
+            return self.coplanar_overlap(other);
+        }
+
+        let second = other.plane_distances(self, tolerance);
+        if second.iter().all(|d| *d > Float::ZERO) || second.iter().all(|d| *d < Float::ZERO) {
+            return false;
+        }
+
+        // Both triangles cross the line shared by their planes, they overlap
+        // where the intervals they cover along it do
+        let direction = self.scaled_normal().cross(&other.scaled_normal());
+        let along = |p: &Point| direction.dot(&p.as_vector());
+        let (a0, a1) = self.plane_crossing(&first, &direction);
+        let (b0, b1) = other.plane_crossing(&second, &direction);
+        along(&a0) <= along(&b1) + tolerance * direction.length()
+            && along(&b0) <= along(&a1) + tolerance * direction.length()
+    }
+
+    fn intersection(&self, other: &Self) -> Self::Intersection {
+        let tolerance = Float::from(TOLERANCE) * Float::max(self.scale(), other.scale());
+        if let Some(crossing) = self.collapsed_crossing(other, tolerance) {
+            return crossing.map(|point| LineSegment::new(point, point));
+        }
+
+        if !self.interesects(other) {
+            return None;
+        }
+
+        let first = self.plane_distances(other, tolerance);
+        if first.iter().all(|d| *d == Float::ZERO) {
+            return None;
+        }
+        let second = other.plane_distances(self, tolerance);
+
+        let direction = self.scaled_normal().cross(&other.scaled_normal());
+        let along = |p: &Point| direction.dot(&p.as_vector());
+        let (a0, a1) = self.plane_crossing(&first, &direction);
+        let (b0, b1) = other.plane_crossing(&second, &direction);
+
+        let start = if along(&a0) >= along(&b0) { a0 } else { b0 };
+        let end = if along(&a1) <= along(&b1) { a1 } else { b1 };
+        Some(LineSegment::new(start, end))
+    }
+}
+
+#[cfg(test)]
+mod triangle_tests {
+    use super::*;
+
+    const EPSILON: Float = 1e-9;
+
+    fn xy_triangle() -> Triangle {
+        Triangle::new(
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(2.0, 0.0, 0.0),
+            Point::new(0.0, 2.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn measurements() {
+        let triangle = xy_triangle();
+        assert!(triangle.normal().approximately(Vector::unit_z(), EPSILON));
+        assert!(triangle.area().approximately(2.0, EPSILON));
+        assert!(triangle
+            .centroid()
+            .approximately(&Point::new(2.0 / 3.0, 2.0 / 3.0, 0.0), EPSILON));
+        assert!(!triangle.is_degenerate());
+
+        let collinear = Triangle::new(
+            Point::origin(),
+            Point::new(1.0, 1.0, 1.0),
+            Point::new(2.0, 2.0, 2.0),
+        );
+        assert!(collinear.is_degenerate());
+        assert!(collinear.barycentric(&Point::origin()).is_none());
+    }
+
+    #[test]
+    fn barycentric_round_trip() {
+        let triangle = Triangle::new(
+            Point::new(1.0, -2.0, 0.5),
+            Point::new(3.0, 1.0, -1.0),
+            Point::new(-1.0, 2.0, 2.0),
+        );
+        let (u, v, w) = triangle.barycentric(&triangle.b).unwrap();
+        assert!(u.approximately(0.0, EPSILON));
+        assert!(v.approximately(1.0, EPSILON));
+        assert!(w.approximately(0.0, EPSILON));
+
+        let point = triangle.from_barycentric(0.2, 0.3, 0.5);
+        let (u, v, w) = triangle.barycentric(&point).unwrap();
+        assert!(u.approximately(0.2, EPSILON));
+        assert!(v.approximately(0.3, EPSILON));
+        assert!(w.approximately(0.5, EPSILON));
+
+        let (u, v, w) = triangle.barycentric(&triangle.centroid()).unwrap();
+        assert!(u.approximately(1.0 / 3.0, EPSILON));
+        assert!(v.approximately(1.0 / 3.0, EPSILON));
+        assert!(w.approximately(1.0 / 3.0, EPSILON));
+    }
+
+    #[test]
+    fn closest_point_regions() {
+        let triangle = xy_triangle();
+        let cases = [
+            // Above the face
+            (Point::new(0.5, 0.5, 3.0), Point::new(0.5, 0.5, 0.0)),
+            // Beyond each vertex
+            (Point::new(-1.0, -1.0, 0.0), Point::new(0.0, 0.0, 0.0)),
+            (Point::new(4.0, -1.0, 1.0), Point::new(2.0, 0.0, 0.0)),
+            (Point::new(-1.0, 4.0, -1.0), Point::new(0.0, 2.0, 0.0)),
+            // Beyond each edge
+            (Point::new(1.0, -1.0, 0.0), Point::new(1.0, 0.0, 0.0)),
+            (Point::new(-1.0, 1.0, 0.0), Point::new(0.0, 1.0, 0.0)),
+            (Point::new(2.0, 2.0, 0.0), Point::new(1.0, 1.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert!(triangle
+                .closest_point(&point)
+                .approximately(&expected, EPSILON));
+        }
+        assert!(triangle
+            .distance_to(&Point::new(2.0, 2.0, 0.0))
+            .approximately(Float::sqrt(2.0), EPSILON));
+    }
+
+    #[test]
+    fn contains() {
+        let triangle = xy_triangle();
+        assert!(triangle.contains(&Point::new(0.5, 0.5, 0.0), EPSILON));
+        assert!(triangle.contains(&Point::new(1.0, 1.0, 0.0), EPSILON));
+        assert!(!triangle.contains(&Point::new(1.5, 1.5, 0.0), EPSILON));
+        assert!(!triangle.contains(&Point::new(0.5, 0.5, 0.1), EPSILON));
+    }
+
+    #[test]
+    fn into_plane() {
+        let triangle = Triangle::new(
+            Point::new(0.0, 0.0, 3.0),
+            Point::new(1.0, 0.0, 3.0),
+            Point::new(0.0, 1.0, 3.0),
+        );
+        let plane = triangle.plane();
+        assert!(plane
+            .norm
+            .normalized()
+            .approximately(Vector::unit_z(), EPSILON));
+        assert!(plane
+            .distance_to(Point::new(5.0, -2.0, 3.0))
+            .approximately(0.0, EPSILON));
+    }
+
+    #[test]
+    fn ray_hit() {
+        let triangle = xy_triangle();
+        let ray = Ray::new(Point::new(0.5, 0.5, 2.0), -Vector::unit_z());
+
+        let hit = ray.cast(&triangle).unwrap();
+        assert!(hit.t.approximately(2.0, EPSILON));
+        assert!(hit.point.approximately(&Point::new(0.5, 0.5, 0.0), EPSILON));
+        assert!(hit.normal.approximately(Vector::unit_z(), EPSILON));
+
+        // Back faces are hit too, with the normal turned toward the ray
+        let below = Ray::new(Point::new(0.5, 0.5, -2.0), Vector::unit_z());
+        let hit = below.cast(&triangle).unwrap();
+        assert!(hit.normal.approximately(-Vector::unit_z(), EPSILON));
+    }
+
+    #[test]
+    fn ray_miss() {
+        let triangle = xy_triangle();
+        assert!(!triangle.interesects(&Ray::new(Point::new(1.5, 1.5, 2.0), -Vector::unit_z())));
+        assert!(!triangle.interesects(&Ray::new(Point::new(0.5, 0.5, 2.0), Vector::unit_z())));
+        assert!(!triangle.interesects(&Ray::new(Point::new(0.5, 0.5, 2.0), Vector::unit_x())));
+    }
+
+    #[test]
+    fn crossing_triangles() {
+        let first = xy_triangle();
+        let second = Triangle::new(
+            Point::new(0.5, -1.0, -1.0),
+            Point::new(0.5, 3.0, -1.0),
+            Point::new(0.5, 0.5, 1.0),
+        );
+        assert!(first.interesects(&second));
+        assert!(second.interesects(&first));
+
+        let segment = first.intersection(&second).unwrap();
+        let (start, end) = if segment.start.y < segment.end.y {
+            (segment.start, segment.end)
+        } else {
+            (segment.end, segment.start)
+        };
+        assert!(start.approximately(&Point::new(0.5, 0.0, 0.0), EPSILON));
+        assert!(end.approximately(&Point::new(0.5, 1.5, 0.0), EPSILON));
+    }
+
+    #[test]
+    fn separated_triangles() {
+        let first = xy_triangle();
+        let above = Triangle::new(
+            Point::new(0.0, 0.0, 1.0),
+            Point::new(1.0, 0.0, 2.0),
+            Point::new(0.0, 1.0, 1.5),
+        );
+        // Crosses the plane of the first triangle, but beside it
+        let beside = Triangle::new(
+            Point::new(3.0, 3.0, -1.0),
+            Point::new(4.0, 3.0, 1.0),
+            Point::new(3.0, 4.0, 1.0),
+        );
+        assert!(!first.interesects(&above));
+        assert!(!first.interesects(&beside));
+        assert!(first.intersection(&beside).is_none());
+    }
+
+    #[test]
+    fn coplanar_triangles() {
+        let first = xy_triangle();
+        let overlapping = Triangle::new(
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(-1.0, 1.0, 0.0),
+            Point::new(1.0, -1.0, 0.0),
+        );
+        let apart = Triangle::new(
+            Point::new(3.0, 3.0, 0.0),
+            Point::new(4.0, 3.0, 0.0),
+            Point::new(3.0, 4.0, 0.0),
+        );
+        let inner = Triangle::new(
+            Point::new(0.1, 0.1, 0.0),
+            Point::new(0.5, 0.1, 0.0),
+            Point::new(0.1, 0.5, 0.0),
+        );
+        assert!(first.interesects(&overlapping));
+        assert!(first.interesects(&inner));
+        assert!(inner.interesects(&first));
+        assert!(!first.interesects(&apart));
+        assert!(first.intersection(&overlapping).is_none());
+    }
+    #[test]
+    fn collapsed_triangles() {
+        let first = xy_triangle();
+        let far = Triangle::new(
+            Point::new(100.0, 100.0, -1.0),
+            Point::new(100.0, 100.0, 0.0),
+            Point::new(100.0, 100.0, 1.0),
+        );
+        assert!(far.is_degenerate());
+        assert!(!first.interesects(&far));
+        assert!(!far.interesects(&first));
+        assert!(first.intersection(&far).is_none());
+
+        // A collapsed triangle piercing the other crosses it at a single point
+        let piercing = Triangle::new(
+            Point::new(0.5, 0.5, -1.0),
+            Point::new(0.5, 0.5, 1.0),
+            Point::new(0.5, 0.5, 0.25),
+        );
+        assert!(first.interesects(&piercing));
+        let segment = piercing.intersection(&first).unwrap();
+        assert!(segment
+            .start
+            .approximately(&Point::new(0.5, 0.5, 0.0), EPSILON));
+        assert!(segment.length().approximately(0.0, EPSILON));
+
+        assert!(!far.interesects(&piercing));
+    }
+}
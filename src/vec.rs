@@ -112,8 +112,8 @@ impl Vector {
     /// Constructs a new unit [Vector] with a direction orthogonal to this vector
     pub fn orthogonal(&self) -> Self {
         let axis = match (self.x.abs(), self.y.abs(), self.z.abs()) {
-            (x, y, z) if x < y && x < z => X_AXIS,
-            (x, y, z) if y < x && y < z => Y_AXIS,
+            (x, y, z) if x <= y && x <= z => X_AXIS,
+            (x, y, z) if y <= x && y <= z => Y_AXIS,
             (_, _, _) => Z_AXIS,
         };
         self.cross(&axis)
@@ -604,6 +604,7 @@ impl Mul<Matrix> for Vector {
 }
 
 #[cfg(test)]
+#[allow(clippy::op_ref)]
 mod vec_tests {
     use super::*;
     use std::f64::consts::PI;
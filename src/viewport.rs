@@ -0,0 +1,303 @@
+//!
+//! Viewports
+//!
+//! A [Viewport] maps normalized device coordinates to and from framebuffer pixels following the
+//! Vulkan conventions of [crate::matrix]: NDC (-1, -1) is the top-left corner of the viewport
+//! with +Y down the screen, and NDC depth in `[0, 1]` maps onto `min_depth..=max_depth`.
+//! Pixel coordinates are continuous, so the center of the top-left pixel is (0.5, 0.5)
+//!
+//! The view and projection matrices both transform column vectors, the view as returned by
+//! [crate::camera::Camera::view_matrix] or the transpose of [Matrix::look_at], and the
+//! projection as by [Matrix::perspective_fov]
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::traits::FloatExt;
+use crate::Float;
+use crate::Point;
+use crate::Vector;
+
+/// A rectangle of the framebuffer that normalized device coordinates are mapped onto
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Viewport {
+    pub x: Float,
+    pub y: Float,
+    pub width: Float,
+    pub height: Float,
+    pub min_depth: Float,
+    pub max_depth: Float,
+}
+
+impl Viewport {
+    /// Constructs a new [Viewport] with its top-left corner at (`x`, `y`) and the default
+    /// depth range of `0..=1`
+    pub fn new<F: Into<Float>>(x: F, y: F, width: F, height: F) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+            width: width.into(),
+            height: height.into(),
+            min_depth: Float::ZERO,
+            max_depth: Float::ONE,
+        }
+    }
+
+    /// Returns a copy of this [Viewport] mapping NDC depth onto `min_depth..=max_depth`
+    pub fn with_depth_range<F: Into<Float>>(&self, min_depth: F, max_depth: F) -> Self {
+        Self {
+            min_depth: min_depth.into(),
+            max_depth: max_depth.into(),
+            ..*self
+        }
+    }
+
+    /// The ratio of width over height, as expected by [Matrix::perspective_fov]
+    #[inline]
+    pub fn aspect(&self) -> Float {
+        self.width / self.height
+    }
+
+    /// Maps a point in normalized device coordinates to a pixel coordinate and framebuffer depth
+    pub fn ndc_to_pixel(&self, ndc: &Point) -> Point {
+        let half = Float::from(0.5);
+        Point::new(
+            self.x + (ndc.x + Float::ONE) * half * self.width,
+            self.y + (ndc.y + Float::ONE) * half * self.height,
+            self.min_depth + ndc.z * (self.max_depth - self.min_depth),
+        )
+    }
+
+    /// Maps a pixel coordinate and framebuffer depth to normalized device coordinates, the
+    /// inverse of [Viewport::ndc_to_pixel]
+    pub fn pixel_to_ndc(&self, pixel: &Point) -> Point {
+        let two = Float::from(2.0);
+        Point::new(
+            (pixel.x - self.x) * two / self.width - Float::ONE,
+            (pixel.y - self.y) * two / self.height - Float::ONE,
+            (pixel.z - self.min_depth) / (self.max_depth - self.min_depth),
+        )
+    }
+
+    /// Projects a world space [Point] to its pixel coordinate, with the framebuffer depth in z
+    ///
+    /// Returns `None` if the point lies on or behind the plane of the camera
+    /// ```
+    /// # use integrator::matrix::*;
+    /// # use integrator::viewport::Viewport;
+    /// # use integrator::{Approximately, Point, Vector};
+    /// let viewport = Viewport::new(0.0, 0.0, 800.0, 600.0);
+    /// let eye = Point::new(0.0, 0.0, 5.0);
+    /// let view = Matrix::look_at(eye, Point::origin(), Vector::unit_y()).transposed();
+    /// let projection = Matrix::perspective_fov(1.0, viewport.aspect(), 0.1, 100.0, ClipSpace::VULKAN);
+    ///
+    /// let pixel = viewport.project(&Point::origin(), &view, &projection).unwrap();
+    /// assert!(pixel.x.approximately(400.0, 1e-9));
+    /// assert!(pixel.y.approximately(300.0, 1e-9));
+    /// ```
+    pub fn project(&self, point: &Point, view: &Matrix, projection: &Matrix) -> Option<Point> {
+        let p = point.as_vector();
+        let [x, y, z, w] = transform(
+            &view_projection(view, projection),
+            [p.x, p.y, p.z, Float::ONE],
+        );
+        (w > Float::ZERO).then(|| self.ndc_to_pixel(&Point::new(x / w, y / w, z / w)))
+    }
+
+    /// Unprojects a pixel coordinate with the framebuffer depth in z back to world space
+    ///
+    /// Returns `None` if the view-projection can not be inverted or the point lies at infinity,
+    /// as the far plane of an infinite projection does
+    pub fn unproject(&self, pixel: &Point, view: &Matrix, projection: &Matrix) -> Option<Point> {
+        let inverse = view_projection(view, projection).try_inverse()?;
+        let ndc = self.pixel_to_ndc(pixel);
+        to_point(transform(&inverse, [ndc.x, ndc.y, ndc.z, Float::ONE]))
+    }
+
+    /// Constructs the world space picking [Ray] through a pixel coordinate
+    ///
+    /// The ray starts on the near plane and points away from the camera. This works for
+    /// perspective and orthographic projections, including reversed-Z and infinite far planes.
+    /// Returns `None` if the view or view-projection can not be inverted
+    pub fn picking_ray<F: Into<Float>>(
+        &self,
+        x: F,
+        y: F,
+        view: &Matrix,
+        projection: &Matrix,
+    ) -> Option<Ray> {
+        let inverse = view_projection(view, projection).try_inverse()?;
+        let camera = view.affine_inverse()?;
+        let eye = Point::new(camera[0][3], camera[1][3], camera[2][3]);
+
+        let ndc = self.pixel_to_ndc(&Point::new(x.into(), y.into(), self.min_depth));
+        let at_depth = |depth: Float| transform(&inverse, [ndc.x, ndc.y, depth, Float::ONE]);
+        let (a, b) = (at_depth(Float::ZERO), at_depth(Float::ONE));
+
+        match (to_point(a), to_point(b)) {
+            (Some(a), Some(b)) => {
+                // Reversed-Z puts the near plane at depth 1, so order the points by distance
+                let (near, far) = if (a - eye).length_sq() <= (b - eye).length_sq() {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+                Some(Ray::new(near, (far - near).normalized()))
+            }
+            // One depth is the far plane at infinity, which gives the direction of the ray
+            (Some(near), None) => Some(Ray::new(near, away_from(eye, near, b))),
+            (None, Some(near)) => Some(Ray::new(near, away_from(eye, near, a))),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Combines a view with a projection into a single column vector transform
+fn view_projection(view: &Matrix, projection: &Matrix) -> Matrix {
+    projection * view
+}
+
+/// Applies a [Matrix] to homogeneous coordinates without the perspective divide
+fn transform(matrix: &Matrix, h: [Float; 4]) -> [Float; 4] {
+    std::array::from_fn(|row| (0..4).map(|col| matrix[row][col] * h[col]).sum())
+}
+
+/// Performs the perspective divide, unless the homogeneous point lies at infinity
+fn to_point([x, y, z, w]: [Float; 4]) -> Option<Point> {
+    let scale = x.abs().max(y.abs()).max(z.abs());
+    (w.abs() > Float::EPSILON * scale).then(|| Point::new(x / w, y / w, z / w))
+}
+
+/// The direction of the homogeneous point at infinity `h`, oriented away from `eye` past `near`
+fn away_from(eye: Point, near: Point, [x, y, z, _]: [Float; 4]) -> Vector {
+    let direction = Vector::new(x, y, z).normalized();
+    if direction.dot(&(near - eye)) < Float::ZERO {
+        -direction
+    } else {
+        direction
+    }
+}
+
+#[cfg(test)]
+mod viewport_tests {
+    use super::*;
+    use crate::matrix::ClipSpace;
+    use crate::traits::Approximately;
+
+    const EPSILON: Float = 1e-9;
+
+    fn camera() -> (Viewport, Matrix, Matrix) {
+        let viewport = Viewport::new(10.0, 20.0, 640.0, 480.0);
+        let view = Matrix::look_at(
+            Point::new(2.0, 3.0, 8.0),
+            Point::new(0.0, 1.0, 0.0),
+            Vector::unit_y(),
+        )
+        .transposed();
+        let projection =
+            Matrix::perspective_fov(1.1, viewport.aspect(), 0.5, 50.0, ClipSpace::VULKAN);
+        (viewport, view, projection)
+    }
+
+    #[test]
+    fn ndc_round_trip() {
+        let viewport = Viewport::new(10.0, 20.0, 640.0, 480.0).with_depth_range(0.2, 0.8);
+        let top_left = viewport.ndc_to_pixel(&Point::new(-1.0, -1.0, 0.0));
+        assert!(top_left.approximately(&Point::new(10.0, 20.0, 0.2), EPSILON));
+
+        let ndc = Point::new(0.3, -0.7, 0.9);
+        let back = viewport.pixel_to_ndc(&viewport.ndc_to_pixel(&ndc));
+        assert!(back.approximately(&ndc, EPSILON));
+    }
+
+    #[test]
+    fn project_and_unproject() {
+        let (viewport, view, projection) = camera();
+        let point = Point::new(-1.0, 2.5, -3.0);
+        let pixel = viewport.project(&point, &view, &projection).unwrap();
+        assert!(pixel.z > 0.0 && pixel.z < 1.0);
+        let back = viewport.unproject(&pixel, &view, &projection).unwrap();
+        assert!(back.approximately(&point, 1e-7));
+
+        // The target is at the center of the viewport
+        let center = viewport
+            .project(&Point::new(0.0, 1.0, 0.0), &view, &projection)
+            .unwrap();
+        assert!(center.x.approximately(330.0, EPSILON));
+        assert!(center.y.approximately(260.0, EPSILON));
+
+        // Behind the camera
+        let behind = Point::new(4.0, 5.0, 16.0);
+        assert!(viewport.project(&behind, &view, &projection).is_none());
+    }
+
+    #[test]
+    fn y_points_down_the_screen() {
+        let viewport = Viewport::new(0.0, 0.0, 100.0, 100.0);
+        let view = Matrix::look_at(
+            Point::origin(),
+            Point::new(0.0, 0.0, -1.0),
+            Vector::unit_y(),
+        )
+        .transposed();
+        let projection = Matrix::perspective_fov(1.0, 1.0, 0.1, 10.0, ClipSpace::VULKAN);
+        let above = viewport
+            .project(&Point::new(0.0, 1.0, -5.0), &view, &projection)
+            .unwrap();
+        assert!(above.y < 50.0);
+    }
+
+    #[test]
+    fn picking_ray() {
+        let (viewport, view, projection) = camera();
+        let point = Point::new(1.5, -0.5, 2.0);
+        let pixel = viewport.project(&point, &view, &projection).unwrap();
+        let ray = viewport
+            .picking_ray(pixel.x, pixel.y, &view, &projection)
+            .unwrap();
+
+        // The ray passes through the point and starts on the near plane
+        let eye = Point::new(2.0, 3.0, 8.0);
+        let to_point = (point - ray.origin).normalized();
+        assert!(to_point.approximately(ray.direction, 1e-7));
+        let forward = (Point::new(0.0, 1.0, 0.0) - eye).normalized();
+        assert!((ray.origin - eye).dot(&forward).approximately(0.5, 1e-7));
+    }
+
+    #[test]
+    fn picking_ray_reversed_infinite() {
+        let (viewport, view, _) = camera();
+        let clip = ClipSpace::VULKAN.with_reversed_z(true);
+        let projection = Matrix::perspective_infinite(1.1, viewport.aspect(), 0.5, clip);
+        let point = Point::new(-2.0, 0.0, -20.0);
+        let pixel = viewport.project(&point, &view, &projection).unwrap();
+        let ray = viewport
+            .picking_ray(pixel.x, pixel.y, &view, &projection)
+            .unwrap();
+        let to_point = (point - ray.origin).normalized();
+        assert!(to_point.approximately(ray.direction, 1e-7));
+    }
+
+    #[test]
+    fn picking_ray_orthographic() {
+        let viewport = Viewport::new(0.0, 0.0, 200.0, 100.0);
+        let view = Matrix::look_at(
+            Point::new(0.0, 0.0, 10.0),
+            Point::origin(),
+            Vector::unit_y(),
+        )
+        .transposed();
+        let projection =
+            Matrix::orthographic_off_center(-4.0, 4.0, -2.0, 2.0, 1.0, 20.0, ClipSpace::VULKAN);
+        let ray = viewport
+            .picking_ray(150.0, 25.0, &view, &projection)
+            .unwrap();
+        assert!(ray.direction.approximately(-Vector::unit_z(), EPSILON));
+        assert!(ray
+            .origin
+            .approximately(&Point::new(2.0, 1.0, 9.0), EPSILON));
+    }
+}